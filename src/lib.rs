@@ -1,5 +1,6 @@
 pub mod traits;
 pub mod linalg;
+pub mod vector;
 
 #[cfg(test)]
 mod test {
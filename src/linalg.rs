@@ -1,13 +1,72 @@
-#[macro_use]
-
 use traits::{Zero, One};
 use std::ops::{Index, IndexMut, Add, Sub, Mul, Div};
 use std::cmp::{PartialEq};
 use std::slice::{Iter, IterMut};
 
+mod band;
+mod cholesky;
+mod eigen;
+mod error;
+mod functions;
+mod generalized_eigen;
+mod hessenberg;
+pub mod iterative;
+mod lu;
+mod mixed;
+mod norm;
+mod ops;
+pub mod partial_eigen;
+mod polar;
+mod qr;
+mod rref;
+mod schur;
+mod smatrix;
+mod svd;
+mod sylvester;
+mod symmetric_eigen;
+mod view;
+mod woodbury;
+
+pub use self::band::{BandMatrix, BandLu, Tridiagonal};
+pub use self::cholesky::{Cholesky, Ldlt};
+pub use self::eigen::Eigen;
+pub use self::error::LinalgError;
+pub use self::generalized_eigen::GeneralizedEigen;
+pub use self::hessenberg::Hessenberg;
+pub use self::lu::Lu;
+pub use self::mixed::MixedSolution;
+pub use self::polar::Polar;
+pub use self::qr::{Qr, ColPivQr};
+pub use self::schur::Schur;
+pub use self::smatrix::SMatrix;
+pub use self::svd::Svd;
+pub use self::symmetric_eigen::SymmetricEigen;
+pub use self::view::{MatrixView, MatrixViewMut, ViewIter, ViewIterMut, Rows, Cols, RowsMut, ColsMut};
+
+/// Memory layout of a `Matrix`. Indexing is the same for both orders; only
+/// the layout of the underlying buffer differs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageOrder {
+	RowMajor,
+	ColMajor,
+}
+
+impl StorageOrder {
+	fn flip(self) -> StorageOrder {
+		match self {
+			StorageOrder::RowMajor => StorageOrder::ColMajor,
+			StorageOrder::ColMajor => StorageOrder::RowMajor,
+		}
+	}
+}
+
+/// Dense matrix. Matrices are row-major unless created with `new_col_major`
+/// or converted with `to_order`; new matrices returned by operations are
+/// row-major.
 pub struct Matrix<T> {
 	nrows: usize,
 	ncols: usize,
+	order: StorageOrder,
 	v: Vec<T>,
 }
 
@@ -25,16 +84,30 @@ impl<T> Matrix<T> where T:
 		let v = values.to_vec();
 		assert!(v.len() == nrows * ncols);
 		Matrix {
-			nrows: nrows,
-			ncols: ncols,
-			v: v
+			nrows,
+			ncols,
+			order: StorageOrder::RowMajor,
+			v
+		}
+	}
+
+	/// Creates a column-major matrix from `values` given column by column.
+	pub fn new_col_major(nrows: usize, ncols: usize, values: &[T]) -> Self {
+		let v = values.to_vec();
+		assert!(v.len() == nrows * ncols);
+		Matrix {
+			nrows,
+			ncols,
+			order: StorageOrder::ColMajor,
+			v
 		}
 	}
 
 	pub fn new_zero(nrows: usize, ncols: usize) -> Self {
 		Matrix {
-			nrows: nrows,
-			ncols: ncols,
+			nrows,
+			ncols,
+			order: StorageOrder::RowMajor,
 			v: vec![T::zero(); nrows * ncols],
 		}
 	}
@@ -43,6 +116,7 @@ impl<T> Matrix<T> where T:
 		Matrix {
 			nrows: n,
 			ncols: n,
+			order: StorageOrder::RowMajor,
 			v: {
 				let mut v = Vec::with_capacity(n*n);
 				for i in 0..(n*n) {
@@ -70,17 +144,40 @@ impl<T> Matrix<T> where T:
 		self.ncols
 	}
 
-	pub fn add(&mut self, other: &Matrix<T>) {
-		assert!(self.size() == other.size());
-		for (i,x) in self.v.iter_mut().enumerate() {
-			*x = *x + other.v[i]; 
+	pub fn order(&self) -> StorageOrder {
+		self.order
+	}
+
+	/// The underlying buffer, laid out in `order()`.
+	pub fn as_slice(&self) -> &[T] {
+		&self.v
+	}
+
+	/// Copy of the matrix stored in the given order.
+	pub fn to_order(&self, order: StorageOrder) -> Matrix<T> {
+		if order == self.order {
+			return self.clone();
 		}
+		let mut m = Matrix::new_zero(self.nrows, self.ncols);
+		m.order = order;
+		m.zip_apply(self, |_, y| y);
+		m
 	}
 
-	pub fn sub(&mut self, other: &Matrix<T>) {
+	// Applies `f` elementwise, walking the buffers directly when both
+	// matrices share a layout.
+	fn zip_apply<F: Fn(T, T) -> T>(&mut self, other: &Matrix<T>, f: F) {
 		assert!(self.size() == other.size());
-		for (i,x) in self.v.iter_mut().enumerate() {
-			*x = *x - other.v[i]; 
+		if self.order == other.order {
+			for (x, y) in self.v.iter_mut().zip(other.v.iter()) {
+				*x = f(*x, *y);
+			}
+			return;
+		}
+		for i in 0..self.nrows {
+			for j in 0..self.ncols {
+				self[(i, j)] = f(self[(i, j)], other[(i, j)]);
+			}
 		}
 	}
 
@@ -91,21 +188,18 @@ impl<T> Matrix<T> where T:
 	}
 
 	pub fn hadamard(&mut self, other: &Matrix<T>) {
-		assert!(self.size() == other.size());
-		for (x, y) in self.v.iter_mut().zip(other.v.iter()) {
-			*x = *x * *y;
-		}
+		self.zip_apply(other, |x, y| x * y);
 	}
 
 	pub fn kronecker(&self, other: &Matrix<T>) -> Self {
 		let nrows = self.nrows * other.nrows;
 		let ncols = self.ncols * other.ncols;
 		Matrix {
-			nrows: nrows,
-			ncols: ncols,
+			nrows,
+			ncols,
+			order: StorageOrder::RowMajor,
 			v: {
-				let mut v = Vec::with_capacity(nrows * ncols);
-				unsafe {v.set_len(nrows * ncols);}
+				let mut v = vec![T::zero(); nrows * ncols];
 				for sr in 0..self.nrows {
 					for sc in 0..self.ncols {
 						for or in 0..other.nrows {
@@ -125,77 +219,185 @@ impl<T> Matrix<T> where T:
 
 	pub fn vercat(&mut self, other: &Matrix<T>) {
 		assert!(self.ncols == other.ncols);
-		self.nrows = self.nrows + other.nrows;
-		for x in other.v.iter() {
-			self.v.push(*x);
+		if self.order == StorageOrder::RowMajor && other.order == StorageOrder::RowMajor {
+			self.nrows += other.nrows;
+			self.v.extend_from_slice(&other.v);
+			return;
 		}
+		self.concat(other, (self.nrows, 0));
 	}
 
 	pub fn horcat(&mut self, other: &Matrix<T>) {
 		assert!(self.nrows == other.nrows);
-		let mut v: Vec<T> = Vec::with_capacity(self.v.len() + other.v.len());
+		if self.order == StorageOrder::ColMajor && other.order == StorageOrder::ColMajor {
+			self.ncols += other.ncols;
+			self.v.extend_from_slice(&other.v);
+			return;
+		}
+		self.concat(other, (0, self.ncols));
+	}
+
+	// Places `other` at `at` in a grown copy of `self`, keeping its order.
+	fn concat(&mut self, other: &Matrix<T>, at: (usize, usize)) {
+		let nrows = ::std::cmp::max(self.nrows, at.0 + other.nrows);
+		let ncols = ::std::cmp::max(self.ncols, at.1 + other.ncols);
+		let mut m = Matrix::new_zero(nrows, ncols);
+		m.order = self.order;
 		for i in 0..self.nrows {
 			for j in 0..self.ncols {
-				v.push(self[(i,j)]);
+				m[(i, j)] = self[(i, j)];
 			}
+		}
+		for i in 0..other.nrows {
 			for j in 0..other.ncols {
-				v.push(other[(i,j)]);
+				m[(at.0 + i, at.1 + j)] = other[(i, j)];
 			}
 		}
-		self.v = v;
+		*self = m;
 	}
 	
+	/// Transposes in place, reordering the buffer so `order()` is unchanged.
+	/// Use `into_transpose` to transpose without copying.
 	pub fn transpose(&mut self) {
 		let mut v = Vec::with_capacity(self.nrows * self.ncols);
-		let ncols = self.ncols;
-		let nrows = self.nrows;
-		for col in 0..ncols {
-			for row in 0..nrows {
-				v.push(self.v[row * self.ncols + col]);
+		let (outer, inner) = match self.order {
+			StorageOrder::RowMajor => (self.ncols, self.nrows),
+			StorageOrder::ColMajor => (self.nrows, self.ncols),
+		};
+		for a in 0..outer {
+			for b in 0..inner {
+				v.push(self.v[b * outer + a]);
 			}
 		}
 		self.v = v;
-		self.ncols = nrows;
-		self.nrows = ncols;
+		::std::mem::swap(&mut self.nrows, &mut self.ncols);
+	}
+
+	/// Transpose that reinterprets the buffer in the opposite storage order
+	/// instead of moving any elements.
+	pub fn into_transpose(self) -> Matrix<T> {
+		Matrix {
+			nrows: self.ncols,
+			ncols: self.nrows,
+			order: self.order.flip(),
+			v: self.v,
+		}
 	}
 
 	pub fn equals(&self, other: &Matrix<T>) -> bool {
 		if self.size() != other.size() {
 			return false;
 		} 
-		for (x, y) in self.v.iter().zip(other.v.iter()) {
-			if *x != *y {
-				return false;
+		if self.order == other.order {
+			return self.v.iter().zip(other.v.iter()).all(|(x, y)| *x == *y);
+		}
+		for i in 0..self.nrows {
+			for j in 0..self.ncols {
+				if self[(i, j)] != other[(i, j)] {
+					return false;
+				}
 			}
 		}
-		return true
+		true
 	}
 
 	pub fn is_square(&self) -> bool {
 		self.nrows == self.ncols
 	}
 
-	pub fn iter(&self) -> Iter<T> {
+	/// Iterates over the elements in storage order.
+	pub fn iter(&self) -> Iter<'_, T> {
 		self.v.iter()
 	}
 
-	pub fn iter_mut(&mut self) -> IterMut<T> {
+	/// Iterates over the elements in storage order.
+	pub fn iter_mut(&mut self) -> IterMut<'_, T> {
 		self.v.iter_mut()
 	}
 
+	pub fn matmul(&self, other: &Matrix<T>) -> Matrix<T> {
+		assert!(self.ncols == other.nrows);
+		let mut m = Matrix::new_zero(self.nrows, other.ncols);
+		for i in 0..self.nrows {
+			for k in 0..self.ncols {
+				let a = self[(i, k)];
+				for j in 0..other.ncols {
+					m[(i, j)] = m[(i, j)] + a * other[(k, j)];
+				}
+			}
+		}
+		m
+	}
+
+	/// Matrix-vector product `A * x`.
+	pub fn mul_vector(&self, x: &Vector<T>) -> Vector<T> {
+		let mut y = Vector::new_zero(self.nrows, 1);
+		self.gemv(T::one(), x, T::zero(), &mut y);
+		y
+	}
+
+	/// Vector-matrix product `x^T * A`.
+	pub fn vector_mul(&self, x: &Vector<T>) -> Vector<T> {
+		assert!(x.size() == self.nrows);
+		let mut y = Vector::new_zero(self.ncols, 1);
+		for i in 0..self.nrows {
+			let a = x[i];
+			for j in 0..self.ncols {
+				y[j] = y[j] + a * self[(i, j)];
+			}
+		}
+		y
+	}
+
+	/// `y = alpha * A * x + beta * y` in place. As in BLAS, `y` is not read
+	/// when `beta` is zero.
+	pub fn gemv(&self, alpha: T, x: &Vector<T>, beta: T, y: &mut Vector<T>) {
+		assert!(x.size() == self.ncols);
+		assert!(y.size() == self.nrows);
+		for i in 0..self.nrows {
+			let mut s = T::zero();
+			for j in 0..self.ncols {
+				s = s + self[(i, j)] * x[j];
+			}
+			y[i] = if beta == T::zero() { alpha * s } else { alpha * s + beta * y[i] };
+		}
+	}
+
+	pub(crate) fn swap_rows(&mut self, a: usize, b: usize) {
+		if a == b {
+			return;
+		}
+		for j in 0..self.ncols {
+			let t = self[(a, j)];
+			self[(a, j)] = self[(b, j)];
+			self[(b, j)] = t;
+		}
+	}
+
+}
+
+impl<T> Matrix<T> {
+	fn offset(&self, index: (usize, usize)) -> usize {
+		debug_assert!(index.0 < self.nrows && index.1 < self.ncols);
+		match self.order {
+			StorageOrder::RowMajor => index.0 * self.ncols + index.1,
+			StorageOrder::ColMajor => index.1 * self.nrows + index.0,
+		}
+	}
 }
 
 impl<T> Index<(usize, usize)> for Matrix<T> {
 	type Output = T;
 
-	fn index<'a>(&'a self, index: (usize, usize)) -> &'a T {
-		&self.v[index.0 * self.ncols + index.1]
+	fn index(&self, index: (usize, usize)) -> &T {
+		&self.v[self.offset(index)]
 	}
 }
 
 impl<T> IndexMut<(usize, usize)> for Matrix<T> {
-	fn index_mut<'a>(&'a mut self, index: (usize, usize)) -> &'a mut T {
-		&mut self.v[index.0 * self.ncols + index.1]
+	fn index_mut(&mut self, index: (usize, usize)) -> &mut T {
+		let i = self.offset(index);
+		&mut self.v[i]
 	}
 }
 
@@ -204,6 +406,7 @@ impl<T: Clone> Clone for Matrix<T> {
 		Matrix {
 			nrows: self.nrows,
 			ncols: self.ncols,
+			order: self.order,
 			v: {
 				let mut v = Vec::with_capacity(self.v.len());
 				for x in self.v.iter() {
@@ -275,17 +478,11 @@ impl<T> Vector<T> where T:
 		self.v.len()
 	}
 
-	pub fn add(&mut self, other: &Vector<T>) {
+	// Applies `f` elementwise.
+	fn zip_apply<F: Fn(T, T) -> T>(&mut self, other: &Vector<T>, f: F) {
 		assert!(self.size() == other.size());
-		for (i,x) in self.v.iter_mut().enumerate() {
-			*x = *x + other.v[i]; 
-		}
-	}
-
-	pub fn sub(&mut self, other: &Vector<T>) {
-		assert!(self.size() == other.size());
-		for (i,x) in self.v.iter_mut().enumerate() {
-			*x = *x - other.v[i]; 
+		for (x, &y) in self.v.iter_mut().zip(other.v.iter()) {
+			*x = f(*x, y);
 		}
 	}
 
@@ -304,21 +501,33 @@ impl<T> Vector<T> where T:
 				return false;
 			}
 		}
-		return true
+		true
 	}	
 
-	pub fn iter(&self) -> Iter<T> {
+	pub fn iter(&self) -> Iter<'_, T> {
 		self.v.iter()
 	}
 
-	pub fn iter_mut(&mut self) -> IterMut<T> {
+	pub fn iter_mut(&mut self) -> IterMut<'_, T> {
 		self.v.iter_mut()
 	}
 
+	/// Outer product `self * other^T`.
+	pub fn outer(&self, other: &Vector<T>) -> Matrix<T> {
+		let mut m = Matrix::new_zero(self.size(), other.size());
+		for (i, &x) in self.v.iter().enumerate() {
+			for (j, &y) in other.v.iter().enumerate() {
+				m[(i, j)] = x * y;
+			}
+		}
+		m
+	}
+
 	pub fn to_row_matrix(self) -> Matrix<T> {
 		Matrix {
 			nrows: 1,
 			ncols: self.v.len(),
+			order: StorageOrder::RowMajor,
 			v: self.v,
 		}
 	}
@@ -327,6 +536,7 @@ impl<T> Vector<T> where T:
 		Matrix {
 			nrows: self.v.len(),
 			ncols: 1,
+			order: StorageOrder::RowMajor,
 			v: self.v,
 		}
 	}
@@ -336,13 +546,13 @@ impl<T> Vector<T> where T:
 impl<T> Index<usize> for Vector<T> {
 	type Output = T;
 
-	fn index<'a>(&'a self, index: usize) -> &'a T {
+	fn index(&self, index: usize) -> &T {
 		&self.v[index]
 	}
 }
 
 impl<T> IndexMut<usize> for Vector<T> {
-	fn index_mut<'a>(&'a mut self, index: usize) -> &'a mut T {
+	fn index_mut(&mut self, index: usize) -> &mut T {
 		&mut self.v[index]
 	}
 }
@@ -361,3 +571,94 @@ impl<T: Clone> Clone for Vector<T> {
 	}
 }
 
+
+#[cfg(test)]
+fn assert_close(a: &Matrix<f64>, b: &Matrix<f64>, tol: f64) {
+	assert!(a.size() == b.size(), "size {:?} != {:?}", a.size(), b.size());
+	for i in 0..a.nrows {
+		for j in 0..a.ncols {
+			assert!((a[(i, j)] - b[(i, j)]).abs() <= tol,
+				"({}, {}): {} != {}", i, j, a[(i, j)], b[(i, j)]);
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::{Matrix, Vector, StorageOrder, assert_close};
+
+	#[test]
+	fn test_col_major() {
+		let r = Matrix::new(2, 3, &[1, 2, 3, 4, 5, 6]);
+		let c = Matrix::new_col_major(2, 3, &[1, 4, 2, 5, 3, 6]);
+		assert_eq!(c.order(), StorageOrder::ColMajor);
+		assert_eq!(c[(0, 2)], 3);
+		assert!(c.equals(&r));
+		assert_eq!(r.to_order(StorageOrder::ColMajor).as_slice(), c.as_slice());
+
+		let mut s = c.clone();
+		s += &r;
+		s.hadamard(&r);
+		assert!(s.equals(&Matrix::new(2, 3, &[2, 8, 18, 32, 50, 72])));
+		assert_eq!(c.matmul(&Matrix::new_identity(3)).as_slice(), r.as_slice());
+		assert_eq!(c.row(1).iter().cloned().collect::<Vec<_>>(), vec![4, 5, 6]);
+		assert_eq!(c.view(0, 1, 2, 2).to_matrix().as_slice(), &[2, 3, 5, 6]);
+
+		let mut v = c.clone();
+		v.vercat(&r);
+		assert!(v.equals(&Matrix::new(4, 3, &[1, 2, 3, 4, 5, 6, 1, 2, 3, 4, 5, 6])));
+		let mut h = c.clone();
+		h.horcat(&c);
+		assert_eq!(h.order(), StorageOrder::ColMajor);
+		assert!(h.equals(&Matrix::new(2, 6, &[1, 2, 3, 1, 2, 3, 4, 5, 6, 4, 5, 6])));
+		let mut h = r.clone();
+		h.horcat(&c);
+		assert!(h.equals(&Matrix::new(2, 6, &[1, 2, 3, 1, 2, 3, 4, 5, 6, 4, 5, 6])));
+
+		let a = Matrix::new_col_major(3, 3, &[4.0, 1.0, 2.0, 1.0, 5.0, 0.0, 2.0, 0.0, 6.0]);
+		let b = Vector::new(&[1.0, 2.0, 3.0]);
+		let x = a.lu().solve(&b).unwrap().to_col_matrix();
+		assert_close(&a.matmul(&x), &b.to_col_matrix(), 1e-12);
+	}
+
+	#[test]
+	fn test_transpose_order() {
+		let m = Matrix::new(2, 3, &[1, 2, 3, 4, 5, 6]);
+		let expected = Matrix::new(3, 2, &[1, 4, 2, 5, 3, 6]);
+
+		let mut t = m.to_order(StorageOrder::ColMajor);
+		t.transpose();
+		assert_eq!(t.order(), StorageOrder::ColMajor);
+		assert!(t.equals(&expected));
+
+		let ptr = m.as_slice().as_ptr();
+		let t = m.into_transpose();
+		assert_eq!(t.as_slice().as_ptr(), ptr);
+		assert_eq!(t.order(), StorageOrder::ColMajor);
+		assert!(t.equals(&expected));
+		let t = t.into_transpose();
+		assert_eq!(t.order(), StorageOrder::RowMajor);
+		assert_eq!(t.as_slice(), &[1, 2, 3, 4, 5, 6]);
+	}
+
+	#[test]
+	fn test_matrix_vector() {
+		let a = Matrix::new(2, 3, &[1, 2, 3, 4, 5, 6]);
+		let x = Vector::new(&[1, 0, 2]);
+		assert!(a.mul_vector(&x).equals(&Vector::new(&[7, 16])));
+		assert!(a.vector_mul(&Vector::new(&[1, 2])).equals(&Vector::new(&[9, 12, 15])));
+		assert!((&a * &x).equals(&a.mul_vector(&x)));
+		assert!((Vector::new(&[1, 2]) * a.clone()).equals(&Vector::new(&[9, 12, 15])));
+
+		let mut y = Vector::new(&[1, 1]);
+		a.gemv(2, &x, 3, &mut y);
+		assert!(y.equals(&Vector::new(&[17, 35])));
+		let mut y = Vector::new(&[f64::NAN; 2]);
+		let b = Matrix::new_col_major(2, 2, &[1.0, 3.0, 2.0, 4.0]);
+		b.gemv(1.0, &Vector::new(&[1.0, 1.0]), 0.0, &mut y);
+		assert!(y.equals(&Vector::new(&[3.0, 7.0])));
+
+		let outer = Vector::new(&[1, 2]).outer(&Vector::new(&[3, 4, 5]));
+		assert!(outer.equals(&Matrix::new(2, 3, &[3, 4, 5, 6, 8, 10])));
+	}
+}
@@ -0,0 +1,414 @@
+use traits::{Zero, One, Float};
+use linalg::{Matrix, Vector, LinalgError};
+use std::ops::{Add, Sub, Mul, Div};
+
+/// Square matrix with `kl` sub- and `ku` superdiagonals. Row `i` stores the
+/// columns `i - kl ..= i + ku`, everything outside the band is zero.
+pub struct BandMatrix<T> {
+	n: usize,
+	kl: usize,
+	ku: usize,
+	v: Vec<T>,
+}
+
+/// Banded LU factorization with partial pivoting. Row interchanges can fill
+/// in up to `kl` extra superdiagonals, so `U` has bandwidth `kl + ku`.
+pub struct BandLu<T> {
+	n: usize,
+	kl: usize,
+	ku: usize,
+	lu: Vec<T>,
+	piv: Vec<usize>,
+	sign: T,
+	singular: bool,
+}
+
+/// Tridiagonal matrix stored as its three diagonals.
+pub struct Tridiagonal<T> {
+	lower: Vec<T>,
+	diag: Vec<T>,
+	upper: Vec<T>,
+}
+
+impl<T> BandMatrix<T> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	pub fn new_zero(n: usize, kl: usize, ku: usize) -> Self {
+		BandMatrix {
+			n,
+			kl,
+			ku,
+			v: vec![T::zero(); n * (kl + ku + 1)],
+		}
+	}
+
+	/// Copies the band of `m`, entries outside of it are ignored.
+	pub fn from_dense(m: &Matrix<T>, kl: usize, ku: usize) -> Self {
+		assert!(m.is_square());
+		let n = m.nrows();
+		let mut b = BandMatrix::new_zero(n, kl, ku);
+		for i in 0..n {
+			for j in b.columns(i) {
+				b.set(i, j, m[(i, j)]);
+			}
+		}
+		b
+	}
+
+	pub fn to_dense(&self) -> Matrix<T> {
+		let mut m = Matrix::new_zero(self.n, self.n);
+		for i in 0..self.n {
+			for j in self.columns(i) {
+				m[(i, j)] = self.get(i, j);
+			}
+		}
+		m
+	}
+
+	pub fn size(&self) -> usize {
+		self.n
+	}
+
+	pub fn lower_bandwidth(&self) -> usize {
+		self.kl
+	}
+
+	pub fn upper_bandwidth(&self) -> usize {
+		self.ku
+	}
+
+	pub fn in_band(&self, i: usize, j: usize) -> bool {
+		i < self.n && j < self.n && j + self.kl >= i && j <= i + self.ku
+	}
+
+	/// Entry `(i, j)`, zero outside the band.
+	pub fn get(&self, i: usize, j: usize) -> T {
+		assert!(i < self.n && j < self.n);
+		if self.in_band(i, j) {
+			self.v[self.offset(i, j)]
+		} else {
+			T::zero()
+		}
+	}
+
+	/// Panics if `(i, j)` lies outside the band.
+	pub fn set(&mut self, i: usize, j: usize, x: T) {
+		assert!(self.in_band(i, j));
+		let k = self.offset(i, j);
+		self.v[k] = x;
+	}
+
+	pub fn mul_vector(&self, x: &Vector<T>) -> Vector<T> {
+		assert!(x.size() == self.n);
+		let mut y = Vector::new(&vec![T::zero(); self.n]);
+		for i in 0..self.n {
+			let mut s = T::zero();
+			for j in self.columns(i) {
+				s = s + self.v[self.offset(i, j)] * x[j];
+			}
+			y[i] = s;
+		}
+		y
+	}
+
+	fn offset(&self, i: usize, j: usize) -> usize {
+		i * (self.kl + self.ku + 1) + j + self.kl - i
+	}
+
+	fn columns(&self, i: usize) -> ::std::ops::Range<usize> {
+		i.saturating_sub(self.kl)..(i + self.ku + 1).min(self.n)
+	}
+}
+
+impl<T: Float> BandMatrix<T> {
+	/// Pivots at most `n * eps * max|a_ij|` in magnitude are treated as zero,
+	/// as in `Matrix::lu`.
+	pub fn lu(&self) -> BandLu<T> {
+		let (n, kl, ku) = (self.n, self.kl, self.ku);
+		let scale = self.v.iter().fold(T::zero(), |m, &x| if x.abs() > m { x.abs() } else { m });
+		let tol = T::from_f64(n as f64) * T::epsilon() * scale;
+		let w = 2 * kl + ku + 1;
+		let mut lu = vec![T::zero(); n * w];
+		for i in 0..n {
+			for j in self.columns(i) {
+				lu[i * w + j + kl - i] = self.get(i, j);
+			}
+		}
+		let at = |i: usize, j: usize| i * w + j + kl - i;
+
+		let mut piv = vec![0; n];
+		let mut sign = T::one();
+		let mut singular = false;
+		for k in 0..n {
+			let last = (k + kl + 1).min(n);
+			let right = (k + kl + ku + 1).min(n);
+			let mut p = k;
+			let mut max = lu[at(k, k)].abs();
+			for i in (k + 1)..last {
+				let a = lu[at(i, k)].abs();
+				if a > max {
+					max = a;
+					p = i;
+				}
+			}
+			piv[k] = p;
+			if p != k {
+				// Multipliers left of column k stay put, as in LAPACK gbtrf
+				for j in k..right {
+					lu.swap(at(k, j), at(p, j));
+				}
+				sign = -sign;
+			}
+			let pivot = lu[at(k, k)];
+			if pivot.abs() <= tol {
+				singular = true;
+				continue;
+			}
+			for i in (k + 1)..last {
+				let l = lu[at(i, k)] / pivot;
+				lu[at(i, k)] = l;
+				if l == T::zero() {
+					continue;
+				}
+				for j in (k + 1)..right {
+					lu[at(i, j)] = lu[at(i, j)] - l * lu[at(k, j)];
+				}
+			}
+		}
+		BandLu {
+			n,
+			kl,
+			ku,
+			lu,
+			piv,
+			sign,
+			singular,
+		}
+	}
+}
+
+impl<T: Float> BandLu<T> {
+	pub fn is_singular(&self) -> bool {
+		self.singular
+	}
+
+	pub fn determinant(&self) -> T {
+		let mut d = self.sign;
+		for i in 0..self.n {
+			d = d * self.lu[self.at(i, i)];
+		}
+		d
+	}
+
+	pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, LinalgError> {
+		let n = self.n;
+		assert!(b.size() == n);
+		if self.singular {
+			return Err(LinalgError::Singular);
+		}
+		let mut x = b.clone();
+		for k in 0..n {
+			let p = self.piv[k];
+			if p != k {
+				let t = x[k];
+				x[k] = x[p];
+				x[p] = t;
+			}
+			for i in (k + 1)..(k + self.kl + 1).min(n) {
+				x[i] = x[i] - self.lu[self.at(i, k)] * x[k];
+			}
+		}
+		for i in (0..n).rev() {
+			let mut s = x[i];
+			for j in (i + 1)..(i + self.kl + self.ku + 1).min(n) {
+				s = s - self.lu[self.at(i, j)] * x[j];
+			}
+			x[i] = s / self.lu[self.at(i, i)];
+		}
+		Ok(x)
+	}
+
+	fn at(&self, i: usize, j: usize) -> usize {
+		i * (2 * self.kl + self.ku + 1) + j + self.kl - i
+	}
+}
+
+impl<T> Tridiagonal<T> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	/// `lower` and `upper` hold the `n - 1` sub- and superdiagonal entries.
+	pub fn new(lower: &[T], diag: &[T], upper: &[T]) -> Self {
+		let n = diag.len();
+		assert!(lower.len() + 1 == n.max(1) && upper.len() == lower.len());
+		Tridiagonal {
+			lower: lower.to_vec(),
+			diag: diag.to_vec(),
+			upper: upper.to_vec(),
+		}
+	}
+
+	pub fn from_dense(m: &Matrix<T>) -> Self {
+		assert!(m.is_square());
+		let n = m.nrows();
+		Tridiagonal {
+			lower: (1..n).map(|i| m[(i, i - 1)]).collect(),
+			diag: (0..n).map(|i| m[(i, i)]).collect(),
+			upper: (1..n).map(|i| m[(i - 1, i)]).collect(),
+		}
+	}
+
+	pub fn to_dense(&self) -> Matrix<T> {
+		self.to_band().to_dense()
+	}
+
+	pub fn to_band(&self) -> BandMatrix<T> {
+		let n = self.diag.len();
+		let mut b = BandMatrix::new_zero(n, 1, 1);
+		for i in 0..n {
+			b.set(i, i, self.diag[i]);
+			if i > 0 {
+				b.set(i, i - 1, self.lower[i - 1]);
+				b.set(i - 1, i, self.upper[i - 1]);
+			}
+		}
+		b
+	}
+
+	pub fn size(&self) -> usize {
+		self.diag.len()
+	}
+
+	pub fn lower(&self) -> &[T] {
+		&self.lower
+	}
+
+	pub fn diag(&self) -> &[T] {
+		&self.diag
+	}
+
+	pub fn upper(&self) -> &[T] {
+		&self.upper
+	}
+
+	pub fn mul_vector(&self, x: &Vector<T>) -> Vector<T> {
+		let n = self.diag.len();
+		assert!(x.size() == n);
+		let mut y = Vector::new(&vec![T::zero(); n]);
+		for i in 0..n {
+			let mut s = self.diag[i] * x[i];
+			if i > 0 {
+				s = s + self.lower[i - 1] * x[i - 1];
+			}
+			if i + 1 < n {
+				s = s + self.upper[i] * x[i + 1];
+			}
+			y[i] = s;
+		}
+		y
+	}
+
+	/// Thomas algorithm, Gaussian elimination without pivoting in `O(n)`.
+	/// Stable for diagonally dominant or symmetric positive definite
+	/// matrices; use `to_band().lu()` otherwise.
+	pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, LinalgError> {
+		let n = self.diag.len();
+		assert!(b.size() == n);
+		let mut c = vec![T::zero(); n];
+		let mut x = b.clone();
+		for i in 0..n {
+			let mut d = self.diag[i];
+			if i > 0 {
+				d = d - self.lower[i - 1] * c[i - 1];
+				x[i] = x[i] - self.lower[i - 1] * x[i - 1];
+			}
+			if d == T::zero() {
+				return Err(LinalgError::Singular);
+			}
+			if i + 1 < n {
+				c[i] = self.upper[i] / d;
+			}
+			x[i] = x[i] / d;
+		}
+		for i in (0..n.saturating_sub(1)).rev() {
+			x[i] = x[i] - c[i] * x[i + 1];
+		}
+		Ok(x)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, Vector, BandMatrix, Tridiagonal, LinalgError};
+
+	#[test]
+	fn test_band_lu() {
+		// Needs pivoting: the leading entry is zero
+		let a: Matrix<f64> = Matrix::new(5,5, &[
+			0.0,2.0,1.0,0.0,0.0,
+			3.0,1.0,4.0,1.0,0.0,
+			1.0,5.0,2.0,6.0,2.0,
+			0.0,2.0,7.0,1.0,8.0,
+			0.0,0.0,3.0,2.0,9.0]);
+		let band = BandMatrix::from_dense(&a, 2, 2);
+		assert!(band.to_dense().equals(&a));
+
+		let x = Vector::new(&[1.0, -2.0, 3.0, 0.5, -1.0]);
+		let b = band.mul_vector(&x);
+		let dense = a.lu().solve(&b).unwrap();
+		for i in 0..5 {
+			assert!((b[i] - (0..5).map(|j| a[(i, j)] * x[j]).sum::<f64>()).abs() < 1e-12);
+		}
+
+		let lu = band.lu();
+		let y = lu.solve(&b).unwrap();
+		for i in 0..5 {
+			assert!((y[i] - x[i]).abs() < 1e-12);
+			assert!((y[i] - dense[i]).abs() < 1e-12);
+		}
+		assert!((lu.determinant() - a.determinant()).abs() < 1e-9);
+		assert_eq!(band.get(0, 4), 0.0);
+	}
+
+	#[test]
+	fn test_band_lu_singular() {
+		// Rank 2, but the last pivot is rounding noise rather than zero
+		let a: Matrix<f64> = Matrix::new(3,3, &[1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0,9.0]);
+		let lu = BandMatrix::from_dense(&a, 2, 2).lu();
+		assert!(lu.is_singular());
+		assert!(lu.solve(&Vector::new(&[1.0,2.0,3.0])).err() == Some(LinalgError::Singular));
+	}
+
+	#[test]
+	fn test_tridiagonal() {
+		let t = Tridiagonal::new(&[-1.0, -1.0, -1.0], &[2.0, 2.0, 2.0, 2.0], &[-1.0, -1.0, -1.0]);
+		let dense: Matrix<f64> = t.to_dense();
+		assert!(Tridiagonal::from_dense(&dense).to_dense().equals(&dense));
+
+		let x = Vector::new(&[1.0, 2.0, 3.0, 4.0]);
+		let b = t.mul_vector(&x);
+		assert!(b.equals(&Vector::new(&[0.0, 0.0, 0.0, 5.0])));
+		let y = t.solve(&b).unwrap();
+		let z = t.to_band().lu().solve(&b).unwrap();
+		for i in 0..4 {
+			assert!((y[i] - x[i]).abs() < 1e-12);
+			assert!((z[i] - x[i]).abs() < 1e-12);
+		}
+
+		let singular = Tridiagonal::new(&[1.0], &[1.0, 1.0], &[1.0]);
+		assert!(singular.solve(&Vector::new(&[1.0, 1.0])).is_err());
+	}
+}
@@ -0,0 +1,341 @@
+use std::cmp::Ordering;
+use traits::Float;
+use linalg::{Matrix, Vector, LinalgError};
+
+/// Cholesky factorization `A = L * L^T` of a symmetric positive definite
+/// matrix. Only the lower triangle of `A` is read.
+pub struct Cholesky<T> {
+	l: Matrix<T>,
+}
+
+/// `A = L * D * L^T` with unit lower triangular `L` and diagonal `D`.
+/// Only the lower triangle of `A` is read. No pivoting is done, so
+/// semidefinite matrices are accepted as long as every zero pivot has a zero
+/// column below it.
+pub struct Ldlt<T> {
+	l: Matrix<T>,
+	d: Vec<T>,
+}
+
+impl<T: Float> Matrix<T> {
+	pub fn cholesky(&self) -> Result<Cholesky<T>, LinalgError> {
+		assert!(self.is_square());
+		let n = self.nrows;
+		let mut l = Matrix::new_zero(n, n);
+		for j in 0..n {
+			let mut d = self[(j, j)];
+			for k in 0..j {
+				d = d - l[(j, k)] * l[(j, k)];
+			}
+			// Also rejects NaN
+			if d.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+				return Err(LinalgError::NotPositiveDefinite);
+			}
+			let d = d.sqrt();
+			l[(j, j)] = d;
+			for i in (j + 1)..n {
+				let mut s = self[(i, j)];
+				for k in 0..j {
+					s = s - l[(i, k)] * l[(j, k)];
+				}
+				l[(i, j)] = s / d;
+			}
+		}
+		Ok(Cholesky {
+			l,
+		})
+	}
+
+	/// Pivots at most `n * eps * max|a_ij|` in magnitude are treated as zero,
+	/// as in `Matrix::lu`.
+	pub fn ldlt(&self) -> Result<Ldlt<T>, LinalgError> {
+		assert!(self.is_square());
+		let n = self.nrows;
+		let mut scale = T::zero();
+		for i in 0..n {
+			for j in 0..(i + 1) {
+				if self[(i, j)].abs() > scale {
+					scale = self[(i, j)].abs();
+				}
+			}
+		}
+		let tol = T::from_f64(n as f64) * T::epsilon() * scale;
+		let mut l = Matrix::new_identity(n);
+		let mut d = vec![T::zero(); n];
+		for j in 0..n {
+			let mut dj = self[(j, j)];
+			for k in 0..j {
+				dj = dj - l[(j, k)] * l[(j, k)] * d[k];
+			}
+			if dj.abs() <= tol {
+				dj = T::zero();
+			}
+			d[j] = dj;
+			for i in (j + 1)..n {
+				let mut s = self[(i, j)];
+				for k in 0..j {
+					s = s - l[(i, k)] * l[(j, k)] * d[k];
+				}
+				if dj == T::zero() {
+					if s.abs() > tol {
+						return Err(LinalgError::Singular);
+					}
+				} else {
+					l[(i, j)] = s / dj;
+				}
+			}
+		}
+		Ok(Ldlt {
+			l,
+			d,
+		})
+	}
+}
+
+// Solves `L * x = b` in place, column by column.
+fn forward<T: Float>(l: &Matrix<T>, x: &mut Matrix<T>) {
+	let n = l.nrows();
+	for c in 0..x.ncols() {
+		for i in 0..n {
+			let mut s = x[(i, c)];
+			for k in 0..i {
+				s = s - l[(i, k)] * x[(k, c)];
+			}
+			x[(i, c)] = s / l[(i, i)];
+		}
+	}
+}
+
+// Solves `L^T * x = b` in place, column by column.
+fn backward<T: Float>(l: &Matrix<T>, x: &mut Matrix<T>) {
+	let n = l.nrows();
+	for c in 0..x.ncols() {
+		for i in (0..n).rev() {
+			let mut s = x[(i, c)];
+			for k in (i + 1)..n {
+				s = s - l[(k, i)] * x[(k, c)];
+			}
+			x[(i, c)] = s / l[(i, i)];
+		}
+	}
+}
+
+impl<T: Float> Cholesky<T> {
+	pub fn l(&self) -> Matrix<T> {
+		self.l.clone()
+	}
+
+	pub fn solve(&self, b: &Vector<T>) -> Vector<T> {
+		let x = self.solve_matrix(&b.clone().to_col_matrix());
+		Vector { v: x.v }
+	}
+
+	pub fn solve_matrix(&self, b: &Matrix<T>) -> Matrix<T> {
+		assert!(b.nrows() == self.l.nrows());
+		let mut x = b.clone();
+		forward(&self.l, &mut x);
+		backward(&self.l, &mut x);
+		x
+	}
+
+	pub fn inverse(&self) -> Matrix<T> {
+		self.solve_matrix(&Matrix::new_identity(self.l.nrows()))
+	}
+
+	pub fn determinant(&self) -> T {
+		let mut d = T::one();
+		for i in 0..self.l.nrows() {
+			d = d * self.l[(i, i)] * self.l[(i, i)];
+		}
+		d
+	}
+
+	/// `ln(det(A))`, without the overflow risk of `determinant`.
+	pub fn log_determinant(&self) -> T {
+		let mut d = T::zero();
+		for i in 0..self.l.nrows() {
+			d = d + self.l[(i, i)].ln();
+		}
+		d + d
+	}
+
+	/// Turns the factorization of `A` into that of `A + x * x^T` in `O(n^2)`.
+	/// This only fails, leaving the factorization unchanged, if `x` is not
+	/// finite.
+	pub fn update(&mut self, x: &Vector<T>) -> Result<(), LinalgError> {
+		self.l = rank_one(&self.l, x, T::one())?;
+		Ok(())
+	}
+
+	/// Turns the factorization of `A` into that of `A - x * x^T`. Fails, and
+	/// leaves the factorization unchanged, if the result is not positive
+	/// definite.
+	pub fn downdate(&mut self, x: &Vector<T>) -> Result<(), LinalgError> {
+		self.l = rank_one(&self.l, x, -T::one())?;
+		Ok(())
+	}
+}
+
+// Cholesky factor of `L * L^T + sigma * x * x^T` for `sigma` = +-1, using
+// the hyperbolic rotations of LINPACK dchud/dchdd.
+fn rank_one<T: Float>(l: &Matrix<T>, x: &Vector<T>, sigma: T) -> Result<Matrix<T>, LinalgError> {
+	let n = l.nrows();
+	assert!(x.size() == n);
+	let mut l = l.clone();
+	let mut x = x.clone();
+	for k in 0..n {
+		let lkk = l[(k, k)];
+		let r2 = lkk * lkk + sigma * x[k] * x[k];
+		if r2.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+			return Err(LinalgError::NotPositiveDefinite);
+		}
+		let r = r2.sqrt();
+		let c = r / lkk;
+		let s = x[k] / lkk;
+		l[(k, k)] = r;
+		for i in (k + 1)..n {
+			l[(i, k)] = (l[(i, k)] + sigma * s * x[i]) / c;
+			x[i] = c * x[i] - s * l[(i, k)];
+		}
+	}
+	Ok(l)
+}
+
+impl<T: Float> Ldlt<T> {
+	pub fn l(&self) -> Matrix<T> {
+		self.l.clone()
+	}
+
+	pub fn d(&self) -> &[T] {
+		&self.d
+	}
+
+	pub fn is_positive_definite(&self) -> bool {
+		self.d.iter().all(|&x| x > T::zero())
+	}
+
+	pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, LinalgError> {
+		let x = self.solve_matrix(&b.clone().to_col_matrix())?;
+		Ok(Vector { v: x.v })
+	}
+
+	pub fn solve_matrix(&self, b: &Matrix<T>) -> Result<Matrix<T>, LinalgError> {
+		assert!(b.nrows() == self.l.nrows());
+		if self.d.iter().any(|&x| x == T::zero()) {
+			return Err(LinalgError::Singular);
+		}
+		let mut x = b.clone();
+		forward(&self.l, &mut x);
+		for (i, &d) in self.d.iter().enumerate() {
+			for c in 0..x.ncols() {
+				x[(i, c)] = x[(i, c)] / d;
+			}
+		}
+		backward(&self.l, &mut x);
+		Ok(x)
+	}
+
+	pub fn inverse(&self) -> Result<Matrix<T>, LinalgError> {
+		self.solve_matrix(&Matrix::new_identity(self.l.nrows()))
+	}
+
+	pub fn determinant(&self) -> T {
+		self.d.iter().fold(T::one(), |acc, &x| acc * x)
+	}
+
+	/// `ln(|det(A)|)`, without the overflow risk of `determinant`.
+	pub fn log_determinant(&self) -> T {
+		self.d.iter().fold(T::zero(), |acc, &x| acc + x.abs().ln())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, Vector, LinalgError, assert_close};
+
+	#[test]
+	fn test_cholesky() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[4.0,12.0,-16.0,12.0,37.0,-43.0,-16.0,-43.0,98.0]);
+		let chol = a.cholesky().unwrap();
+		let l: Matrix<f64> = Matrix::new(3,3, &[2.0,0.0,0.0,6.0,1.0,0.0,-8.0,5.0,3.0]);
+		assert_close(&chol.l(), &l, 1e-12);
+		assert!((chol.determinant() - 36.0).abs() < 1e-10);
+		assert!((chol.log_determinant() - 36.0f64.ln()).abs() < 1e-12);
+		assert_close(&a.matmul(&chol.inverse()), &Matrix::new_identity(3), 1e-10);
+
+		let x = chol.solve(&Vector::new(&[-4.0,0.0,94.0]));
+		for (i, &e) in [1.0, 2.0, 2.0].iter().enumerate() {
+			assert!((x[i] - e).abs() < 1e-10);
+		}
+	}
+
+	#[test]
+	fn test_cholesky_not_positive_definite() {
+		let a: Matrix<f64> = Matrix::new(2,2, &[1.0,2.0,2.0,1.0]);
+		assert!(a.cholesky().err() == Some(LinalgError::NotPositiveDefinite));
+		let b: Matrix<f64> = Matrix::new(2,2, &[1.0,1.0,1.0,1.0]);
+		assert!(b.cholesky().err() == Some(LinalgError::NotPositiveDefinite));
+	}
+
+	#[test]
+	fn test_ldlt() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[4.0,12.0,-16.0,12.0,37.0,-43.0,-16.0,-43.0,98.0]);
+		let ldlt = a.ldlt().unwrap();
+		let mut d = Matrix::new_zero(3, 3);
+		for i in 0..3 {
+			d[(i, i)] = ldlt.d()[i];
+		}
+		let mut lt = ldlt.l();
+		lt.transpose();
+		assert_close(&ldlt.l().matmul(&d).matmul(&lt), &a, 1e-10);
+		assert!(ldlt.is_positive_definite());
+		assert!((ldlt.determinant() - 36.0).abs() < 1e-10);
+	}
+
+	#[test]
+	fn test_ldlt_indefinite() {
+		let a: Matrix<f64> = Matrix::new(2,2, &[1.0,2.0,2.0,1.0]);
+		let ldlt = a.ldlt().unwrap();
+		assert!(!ldlt.is_positive_definite());
+		assert!((ldlt.determinant() + 3.0).abs() < 1e-12);
+		assert!((ldlt.log_determinant() - 3.0f64.ln()).abs() < 1e-12);
+		assert_close(&a.matmul(&ldlt.inverse().unwrap()), &Matrix::new_identity(2), 1e-12);
+	}
+
+	#[test]
+	fn test_ldlt_semidefinite() {
+		let a: Matrix<f64> = Matrix::new(2,2, &[1.0,1.0,1.0,1.0]);
+		let ldlt = a.ldlt().unwrap();
+		assert_eq!(ldlt.d(), &[1.0, 0.0]);
+		assert!(ldlt.inverse().err() == Some(LinalgError::Singular));
+
+		// Rank 2, but the last pivot is rounding noise rather than zero
+		let a: Matrix<f64> = Matrix::new(3,3, &[0.1,0.2,0.3,0.2,0.5,0.8,0.3,0.8,1.3]);
+		let ldlt = a.ldlt().unwrap();
+		assert_eq!(ldlt.d()[2], 0.0);
+		assert!(ldlt.solve(&Vector::new(&[1.0,2.0,3.0])).err() == Some(LinalgError::Singular));
+	}
+
+	#[test]
+	fn test_cholesky_update() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[4.0,12.0,-16.0,12.0,37.0,-43.0,-16.0,-43.0,98.0]);
+		let x = Vector::new(&[1.0, -2.0, 0.5]);
+		let mut b = a.clone();
+		b += &x.clone().to_col_matrix().matmul(&x.clone().to_row_matrix());
+
+		let mut chol = a.cholesky().unwrap();
+		chol.update(&x).unwrap();
+		assert_close(&chol.l(), &b.cholesky().unwrap().l(), 1e-12);
+		chol.downdate(&x).unwrap();
+		assert_close(&chol.l(), &a.cholesky().unwrap().l(), 1e-12);
+
+		// A - y * y^T has a negative eigenvalue
+		let y = Vector::new(&[3.0, 0.0, 0.0]);
+		assert!(chol.downdate(&y).err() == Some(LinalgError::NotPositiveDefinite));
+		assert_close(&chol.l(), &a.cholesky().unwrap().l(), 1e-12);
+
+		let z = Vector::new(&[f64::NAN, 0.0, 0.0]);
+		assert!(chol.update(&z).err() == Some(LinalgError::NotPositiveDefinite));
+		assert_close(&chol.l(), &a.cholesky().unwrap().l(), 1e-12);
+	}
+}
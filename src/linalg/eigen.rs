@@ -0,0 +1,556 @@
+use traits::Float;
+use linalg::{Matrix, LinalgError};
+
+/// Eigen decomposition of a general real square matrix.
+///
+/// Complex eigenvalues come in conjugate pairs stored next to each other,
+/// the one with positive imaginary part first. For such a pair in positions
+/// `j` and `j + 1`, columns `j` and `j + 1` of `vectors` hold the real and
+/// imaginary parts of the eigenvector belonging to `real[j] + i * imag[j]`;
+/// the conjugate eigenvalue has the conjugate eigenvector. Eigenvectors are
+/// scaled to unit 2-norm.
+pub struct Eigen<T> {
+	real: Vec<T>,
+	imag: Vec<T>,
+	vectors: Matrix<T>,
+}
+
+impl<T: Float> Matrix<T> {
+	pub fn eigen(&self) -> Result<Eigen<T>, LinalgError> {
+		assert!(self.is_square());
+		let (mut h, mut v) = self.hessenberg().into_parts();
+		let (real, imag) = francis_qr(&mut h, Some(&mut v))?;
+		eigenvectors(&mut h, &mut v, &real, &imag);
+		Ok(Eigen {
+			real,
+			imag,
+			vectors: v,
+		})
+	}
+
+	/// The real and imaginary parts of the eigenvalues, skipping the
+	/// eigenvector computation.
+	pub fn eigenvalues(&self) -> Result<(Vec<T>, Vec<T>), LinalgError> {
+		assert!(self.is_square());
+		let (mut h, _) = self.hessenberg().into_parts();
+		francis_qr(&mut h, None)
+	}
+}
+
+impl<T> Eigen<T> {
+	pub fn real(&self) -> &[T] {
+		&self.real
+	}
+
+	pub fn imag(&self) -> &[T] {
+		&self.imag
+	}
+
+	pub fn vectors(&self) -> &Matrix<T> {
+		&self.vectors
+	}
+}
+
+// Double shift Francis QR iteration on an upper Hessenberg matrix (EISPACK
+// hqr2). On return `h` is in real Schur form: quasi upper triangular with
+// 1x1 blocks for real eigenvalues and 2x2 blocks for complex pairs. If `v` is
+// given the orthogonal transformations are accumulated into it.
+pub(crate) fn francis_qr<T: Float>(h: &mut Matrix<T>, mut v: Option<&mut Matrix<T>>)
+	-> Result<(Vec<T>, Vec<T>), LinalgError> {
+	let nn = h.nrows();
+	let mut d = vec![T::zero(); nn];
+	let mut e = vec![T::zero(); nn];
+	if nn == 0 {
+		return Ok((d, e));
+	}
+
+	let two = T::one() + T::one();
+	let eps = T::epsilon();
+	let max_iter = 30 * ::std::cmp::max(nn, 10);
+	let mut exshift = T::zero();
+	let (mut p, mut q, mut r, mut s, mut z): (T, T, T, T, T);
+	let (mut w, mut x, mut y): (T, T, T);
+
+	let mut norm = T::zero();
+	for i in 0..nn {
+		for j in i.saturating_sub(1)..nn {
+			norm = norm + h[(i, j)].abs();
+		}
+	}
+
+	let mut n = nn - 1;
+	let mut iter = 0;
+	loop {
+		// Look for a single small subdiagonal element
+		let mut l = n;
+		while l > 0 {
+			s = h[(l - 1, l - 1)].abs() + h[(l, l)].abs();
+			if s == T::zero() {
+				s = norm;
+			}
+			if h[(l, l - 1)].abs() < eps * s {
+				break;
+			}
+			l -= 1;
+		}
+
+		if l == n {
+			// One root found
+			h[(n, n)] = h[(n, n)] + exshift;
+			d[n] = h[(n, n)];
+			e[n] = T::zero();
+			iter = 0;
+			if n == 0 {
+				break;
+			}
+			n -= 1;
+		} else if l + 1 == n {
+			// Two roots found
+			w = h[(n, n - 1)] * h[(n - 1, n)];
+			p = (h[(n - 1, n - 1)] - h[(n, n)]) / two;
+			q = p * p + w;
+			z = q.abs().sqrt();
+			h[(n, n)] = h[(n, n)] + exshift;
+			h[(n - 1, n - 1)] = h[(n - 1, n - 1)] + exshift;
+			x = h[(n, n)];
+
+			if q >= T::zero() {
+				// Real pair
+				z = if p >= T::zero() { p + z } else { p - z };
+				d[n - 1] = x + z;
+				d[n] = d[n - 1];
+				if z != T::zero() {
+					d[n] = x - w / z;
+				}
+				e[n - 1] = T::zero();
+				e[n] = T::zero();
+				x = h[(n, n - 1)];
+				s = x.abs() + z.abs();
+				p = x / s;
+				q = z / s;
+				r = (p * p + q * q).sqrt();
+				p = p / r;
+				q = q / r;
+
+				for j in (n - 1)..nn {
+					z = h[(n - 1, j)];
+					h[(n - 1, j)] = q * z + p * h[(n, j)];
+					h[(n, j)] = q * h[(n, j)] - p * z;
+				}
+				for i in 0..(n + 1) {
+					z = h[(i, n - 1)];
+					h[(i, n - 1)] = q * z + p * h[(i, n)];
+					h[(i, n)] = q * h[(i, n)] - p * z;
+				}
+				if let Some(ref mut v) = v {
+					for i in 0..nn {
+						z = v[(i, n - 1)];
+						v[(i, n - 1)] = q * z + p * v[(i, n)];
+						v[(i, n)] = q * v[(i, n)] - p * z;
+					}
+				}
+			} else {
+				// Complex pair
+				d[n - 1] = x + p;
+				d[n] = x + p;
+				e[n - 1] = z;
+				e[n] = -z;
+			}
+			iter = 0;
+			if n < 2 {
+				break;
+			}
+			n -= 2;
+		} else {
+			// No convergence yet, form the shift
+			x = h[(n, n)];
+			y = h[(n - 1, n - 1)];
+			w = h[(n, n - 1)] * h[(n - 1, n)];
+
+			// Wilkinson's original ad hoc shift
+			if iter == 10 {
+				exshift = exshift + x;
+				for i in 0..(n + 1) {
+					h[(i, i)] = h[(i, i)] - x;
+				}
+				s = h[(n, n - 1)].abs() + h[(n - 1, n - 2)].abs();
+				x = T::from_f64(0.75) * s;
+				y = x;
+				w = T::from_f64(-0.4375) * s * s;
+			}
+
+			// MATLAB's ad hoc shift
+			if iter == 30 {
+				s = (y - x) / two;
+				s = s * s + w;
+				if s > T::zero() {
+					s = s.sqrt();
+					if y < x {
+						s = -s;
+					}
+					s = x - w / ((y - x) / two + s);
+					for i in 0..(n + 1) {
+						h[(i, i)] = h[(i, i)] - s;
+					}
+					exshift = exshift + s;
+					x = T::from_f64(0.964);
+					y = x;
+					w = x;
+				}
+			}
+
+			iter += 1;
+			if iter > max_iter {
+				return Err(LinalgError::NoConvergence);
+			}
+
+			// Look for two consecutive small subdiagonal elements
+			let mut m = n - 2;
+			loop {
+				z = h[(m, m)];
+				r = x - z;
+				s = y - z;
+				p = (r * s - w) / h[(m + 1, m)] + h[(m, m + 1)];
+				q = h[(m + 1, m + 1)] - z - r - s;
+				r = h[(m + 2, m + 1)];
+				s = p.abs() + q.abs() + r.abs();
+				p = p / s;
+				q = q / s;
+				r = r / s;
+				if m == l {
+					break;
+				}
+				if h[(m, m - 1)].abs() * (q.abs() + r.abs()) <
+					eps * (p.abs() * (h[(m - 1, m - 1)].abs() + z.abs() + h[(m + 1, m + 1)].abs())) {
+					break;
+				}
+				m -= 1;
+			}
+
+			for i in (m + 2)..(n + 1) {
+				h[(i, i - 2)] = T::zero();
+				if i > m + 2 {
+					h[(i, i - 3)] = T::zero();
+				}
+			}
+
+			// Double QR step involving rows l..n and columns m..n
+			for k in m..n {
+				let notlast = k != n - 1;
+				if k != m {
+					p = h[(k, k - 1)];
+					q = h[(k + 1, k - 1)];
+					r = if notlast { h[(k + 2, k - 1)] } else { T::zero() };
+					x = p.abs() + q.abs() + r.abs();
+					if x == T::zero() {
+						continue;
+					}
+					p = p / x;
+					q = q / x;
+					r = r / x;
+				}
+
+				s = (p * p + q * q + r * r).sqrt();
+				if p < T::zero() {
+					s = -s;
+				}
+				if s == T::zero() {
+					continue;
+				}
+				if k != m {
+					h[(k, k - 1)] = -s * x;
+				} else if l != m {
+					h[(k, k - 1)] = -h[(k, k - 1)];
+				}
+				p = p + s;
+				x = p / s;
+				y = q / s;
+				z = r / s;
+				q = q / p;
+				r = r / p;
+
+				for j in k..nn {
+					p = h[(k, j)] + q * h[(k + 1, j)];
+					if notlast {
+						p = p + r * h[(k + 2, j)];
+						h[(k + 2, j)] = h[(k + 2, j)] - p * z;
+					}
+					h[(k, j)] = h[(k, j)] - p * x;
+					h[(k + 1, j)] = h[(k + 1, j)] - p * y;
+				}
+				for i in 0..(::std::cmp::min(n, k + 3) + 1) {
+					p = x * h[(i, k)] + y * h[(i, k + 1)];
+					if notlast {
+						p = p + z * h[(i, k + 2)];
+						h[(i, k + 2)] = h[(i, k + 2)] - p * r;
+					}
+					h[(i, k)] = h[(i, k)] - p;
+					h[(i, k + 1)] = h[(i, k + 1)] - p * q;
+				}
+				if let Some(ref mut v) = v {
+					for i in 0..nn {
+						p = x * v[(i, k)] + y * v[(i, k + 1)];
+						if notlast {
+							p = p + z * v[(i, k + 2)];
+							v[(i, k + 2)] = v[(i, k + 2)] - p * r;
+						}
+						v[(i, k)] = v[(i, k)] - p;
+						v[(i, k + 1)] = v[(i, k + 1)] - p * q;
+					}
+				}
+			}
+		}
+	}
+
+	// Clean out the rounding residue below the quasi triangular part
+	for i in 1..nn {
+		for j in 0..(i - 1) {
+			h[(i, j)] = T::zero();
+		}
+		if e[i] == T::zero() {
+			h[(i, i - 1)] = T::zero();
+		}
+	}
+	Ok((d, e))
+}
+
+// Complex scalar division (xr + i*xi) / (yr + i*yi).
+fn cdiv<T: Float>(xr: T, xi: T, yr: T, yi: T) -> (T, T) {
+	if yr.abs() > yi.abs() {
+		let r = yi / yr;
+		let d = yr + r * yi;
+		((xr + r * xi) / d, (xi - r * xr) / d)
+	} else {
+		let r = yr / yi;
+		let d = yi + r * yr;
+		((r * xr + xi) / d, (r * xi - xr) / d)
+	}
+}
+
+// Back substitution on the real Schur form `h` to get the eigenvectors,
+// which are then transformed back with the Schur vectors `v` and normalized.
+// `h` is destroyed and `v` receives the eigenvectors.
+fn eigenvectors<T: Float>(h: &mut Matrix<T>, v: &mut Matrix<T>, d: &[T], e: &[T]) {
+	let nn = h.nrows();
+	let eps = T::epsilon();
+	let two = T::one() + T::one();
+	let mut norm = T::zero();
+	for i in 0..nn {
+		for j in i.saturating_sub(1)..nn {
+			norm = norm + h[(i, j)].abs();
+		}
+	}
+	if norm == T::zero() {
+		return;
+	}
+
+	let (mut r, mut s, mut z) = (T::zero(), T::zero(), T::zero());
+	for n in (0..nn).rev() {
+		let p = d[n];
+		let q = e[n];
+
+		if q == T::zero() {
+			// Real vector
+			let mut l = n;
+			h[(n, n)] = T::one();
+			for i in (0..n).rev() {
+				let w = h[(i, i)] - p;
+				r = T::zero();
+				for j in l..(n + 1) {
+					r = r + h[(i, j)] * h[(j, n)];
+				}
+				if e[i] < T::zero() {
+					z = w;
+					s = r;
+					continue;
+				}
+				l = i;
+				if e[i] == T::zero() {
+					h[(i, n)] = if w != T::zero() { -r / w } else { -r / (eps * norm) };
+				} else {
+					// Solve real equations
+					let x = h[(i, i + 1)];
+					let y = h[(i + 1, i)];
+					let qq = (d[i] - p) * (d[i] - p) + e[i] * e[i];
+					let t = (x * s - z * r) / qq;
+					h[(i, n)] = t;
+					h[(i + 1, n)] = if x.abs() > z.abs() {
+						(-r - w * t) / x
+					} else {
+						(-s - y * t) / z
+					};
+				}
+
+				// Overflow control
+				let t = h[(i, n)].abs();
+				if (eps * t) * t > T::one() {
+					for j in i..(n + 1) {
+						h[(j, n)] = h[(j, n)] / t;
+					}
+				}
+			}
+		} else if q < T::zero() {
+			// Complex vector, the last component is taken imaginary so the
+			// system is triangular
+			let mut l = n - 1;
+			if h[(n, n - 1)].abs() > h[(n - 1, n)].abs() {
+				h[(n - 1, n - 1)] = q / h[(n, n - 1)];
+				h[(n - 1, n)] = -(h[(n, n)] - p) / h[(n, n - 1)];
+			} else {
+				let (cr, ci) = cdiv(T::zero(), -h[(n - 1, n)], h[(n - 1, n - 1)] - p, q);
+				h[(n - 1, n - 1)] = cr;
+				h[(n - 1, n)] = ci;
+			}
+			h[(n, n - 1)] = T::zero();
+			h[(n, n)] = T::one();
+			for i in (0..(n - 1)).rev() {
+				let mut ra = T::zero();
+				let mut sa = T::zero();
+				for j in l..(n + 1) {
+					ra = ra + h[(i, j)] * h[(j, n - 1)];
+					sa = sa + h[(i, j)] * h[(j, n)];
+				}
+				let w = h[(i, i)] - p;
+
+				if e[i] < T::zero() {
+					z = w;
+					r = ra;
+					s = sa;
+					continue;
+				}
+				l = i;
+				if e[i] == T::zero() {
+					let (cr, ci) = cdiv(-ra, -sa, w, q);
+					h[(i, n - 1)] = cr;
+					h[(i, n)] = ci;
+				} else {
+					// Solve complex equations
+					let x = h[(i, i + 1)];
+					let y = h[(i + 1, i)];
+					let mut vr = (d[i] - p) * (d[i] - p) + e[i] * e[i] - q * q;
+					let vi = (d[i] - p) * two * q;
+					if vr == T::zero() && vi == T::zero() {
+						vr = eps * norm * (w.abs() + q.abs() + x.abs() + y.abs() + z.abs());
+					}
+					let (cr, ci) = cdiv(x * r - z * ra + q * sa, x * s - z * sa - q * ra, vr, vi);
+					h[(i, n - 1)] = cr;
+					h[(i, n)] = ci;
+					if x.abs() > z.abs() + q.abs() {
+						h[(i + 1, n - 1)] = (-ra - w * h[(i, n - 1)] + q * h[(i, n)]) / x;
+						h[(i + 1, n)] = (-sa - w * h[(i, n)] - q * h[(i, n - 1)]) / x;
+					} else {
+						let (cr, ci) = cdiv(-r - y * h[(i, n - 1)], -s - y * h[(i, n)], z, q);
+						h[(i + 1, n - 1)] = cr;
+						h[(i + 1, n)] = ci;
+					}
+				}
+
+				// Overflow control
+				let a = h[(i, n - 1)].abs();
+				let b = h[(i, n)].abs();
+				let t = if a > b { a } else { b };
+				if (eps * t) * t > T::one() {
+					for j in i..(n + 1) {
+						h[(j, n - 1)] = h[(j, n - 1)] / t;
+						h[(j, n)] = h[(j, n)] / t;
+					}
+				}
+			}
+		}
+	}
+
+	// Back transformation to eigenvectors of the original matrix
+	for j in (0..nn).rev() {
+		for i in 0..nn {
+			z = T::zero();
+			for k in 0..(j + 1) {
+				z = z + v[(i, k)] * h[(k, j)];
+			}
+			v[(i, j)] = z;
+		}
+	}
+
+	let mut j = 0;
+	while j < nn {
+		let width = if e[j] > T::zero() { 2 } else { 1 };
+		let mut ss = T::zero();
+		for i in 0..nn {
+			for k in j..(j + width) {
+				ss = ss + v[(i, k)] * v[(i, k)];
+			}
+		}
+		let len = ss.sqrt();
+		if len > T::zero() {
+			for i in 0..nn {
+				for k in j..(j + width) {
+					v[(i, k)] = v[(i, k)] / len;
+				}
+			}
+		}
+		j += width;
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, assert_close};
+
+	// A * V == V * D where D holds the 2x2 blocks [re, im; -im, re]
+	fn check_vectors(a: &Matrix<f64>) {
+		let eig = a.eigen().unwrap();
+		let n = a.nrows();
+		let mut d = Matrix::new_zero(n, n);
+		for i in 0..n {
+			d[(i, i)] = eig.real()[i];
+			if eig.imag()[i] > 0.0 {
+				d[(i, i + 1)] = eig.imag()[i];
+			} else if eig.imag()[i] < 0.0 {
+				d[(i, i - 1)] = eig.imag()[i];
+			}
+		}
+		let v = eig.vectors();
+		assert_close(&a.matmul(v), &v.matmul(&d), 1e-10);
+	}
+
+	#[test]
+	fn test_real_eigenvalues() {
+		// Companion matrix of (x - 1)(x - 2)(x - 3)
+		let a: Matrix<f64> = Matrix::new(3,3, &[6.0,-11.0,6.0,1.0,0.0,0.0,0.0,1.0,0.0]);
+		let (mut re, im) = a.eigenvalues().unwrap();
+		re.sort_by(|a, b| a.partial_cmp(b).unwrap());
+		for (x, e) in re.iter().zip([1.0, 2.0, 3.0].iter()) {
+			assert!((x - e).abs() < 1e-10);
+		}
+		assert!(im.iter().all(|&x| x == 0.0));
+		check_vectors(&a);
+
+		let b: Matrix<f64> = Matrix::new(4,4, &[
+			4.0,1.0,-2.0,2.0,
+			3.0,2.0,0.0,1.0,
+			-2.0,5.0,3.0,-2.0,
+			7.0,1.0,-2.0,-1.0]);
+		check_vectors(&b);
+	}
+
+	#[test]
+	fn test_complex_eigenvalues() {
+		let a: Matrix<f64> = Matrix::new(2,2, &[0.0,1.0,-1.0,0.0]);
+		let (re, im) = a.eigenvalues().unwrap();
+		assert!(re.iter().all(|x| x.abs() < 1e-14));
+		assert!((im[0] - 1.0).abs() < 1e-14);
+		assert!((im[1] + 1.0).abs() < 1e-14);
+		check_vectors(&a);
+
+		let b: Matrix<f64> = Matrix::new(4,4, &[
+			1.0,2.0,3.0,4.0,
+			-5.0,6.0,7.0,8.0,
+			9.0,-10.0,11.0,12.0,
+			13.0,14.0,-15.0,16.0]);
+		let (re, im) = b.eigenvalues().unwrap();
+		assert_eq!(im.iter().filter(|&&x| x != 0.0).count(), 2);
+		// Trace is the sum of the eigenvalues
+		assert!((re.iter().fold(0.0, |s, x| s + x) - 34.0).abs() < 1e-10);
+		assert!(im.iter().fold(0.0, |s, x| s + x).abs() < 1e-10);
+		check_vectors(&b);
+	}
+}
@@ -0,0 +1,27 @@
+use std::error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinalgError {
+	/// A pivot vanished during factorization.
+	Singular,
+	/// The matrix does not have full column rank.
+	RankDeficient,
+	/// A Cholesky pivot was not positive.
+	NotPositiveDefinite,
+	/// An iterative method hit its iteration limit.
+	NoConvergence,
+}
+
+impl fmt::Display for LinalgError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			LinalgError::Singular => write!(f, "matrix is singular"),
+			LinalgError::RankDeficient => write!(f, "matrix is rank deficient"),
+			LinalgError::NotPositiveDefinite => write!(f, "matrix is not positive definite"),
+			LinalgError::NoConvergence => write!(f, "iteration did not converge"),
+		}
+	}
+}
+
+impl error::Error for LinalgError {}
@@ -0,0 +1,232 @@
+use traits::{Zero, One, Float};
+use linalg::{Matrix, LinalgError};
+use std::ops::{Add, Sub, Mul, Div};
+
+impl<T> Matrix<T> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	/// `A^n` by binary exponentiation, so it also works for integer matrices.
+	pub fn pow(&self, mut n: u32) -> Matrix<T> {
+		assert!(self.is_square());
+		let mut result = Matrix::new_identity(self.nrows);
+		let mut base = self.clone();
+		while n > 0 {
+			if n & 1 == 1 {
+				result = result.matmul(&base);
+			}
+			n >>= 1;
+			if n > 0 {
+				base = base.matmul(&base);
+			}
+		}
+		result
+	}
+}
+
+// Numerator coefficients of the [13/13] Pade approximant to exp
+const PADE13: [f64; 14] = [
+	64764752532480000.0, 32382376266240000.0, 7771770303897600.0,
+	1187353796428800.0, 129060195264000.0, 10559470521600.0,
+	670442572800.0, 33522128640.0, 1323241920.0, 40840800.0,
+	960960.0, 16380.0, 182.0, 1.0];
+
+// Largest 1-norm for which the [13/13] Pade approximant is accurate to double
+// precision without scaling (Higham, 2005)
+const THETA13: f64 = 5.371920351148152;
+
+fn scaled<T: Float>(a: &Matrix<T>, s: T) -> Matrix<T> {
+	let mut b = a.clone();
+	b.scalar(s);
+	b
+}
+
+impl<T: Float> Matrix<T> {
+	/// Matrix exponential by scaling and squaring with a [13/13] Pade
+	/// approximant.
+	pub fn expm(&self) -> Result<Matrix<T>, LinalgError> {
+		assert!(self.is_square());
+		let n = self.nrows;
+		let norm = self.norm1().to_f64();
+		let s = if norm > THETA13 { (norm / THETA13).log2().ceil() as i32 } else { 0 };
+		let a = scaled(self, T::from_f64(0.5f64.powi(s)));
+
+		let b: Vec<T> = PADE13.iter().map(|&x| T::from_f64(x)).collect();
+		let ident: Matrix<T> = Matrix::new_identity(n);
+		let a2 = a.matmul(&a);
+		let a4 = a2.matmul(&a2);
+		let a6 = a4.matmul(&a2);
+		let sum = |c6: T, c4: T, c2: T, c0: T| {
+			let mut m = scaled(&a6, c6);
+			m += &scaled(&a4, c4);
+			m += &scaled(&a2, c2);
+			m += &scaled(&ident, c0);
+			m
+		};
+
+		let mut u = a6.matmul(&sum(b[13], b[11], b[9], T::zero()));
+		u += &sum(b[7], b[5], b[3], b[1]);
+		let u = a.matmul(&u);
+		let mut v = a6.matmul(&sum(b[12], b[10], b[8], T::zero()));
+		v += &sum(b[6], b[4], b[2], b[0]);
+
+		let mut p = v.clone();
+		p += &u;
+		let mut q = v;
+		q -= &u;
+		let mut r = q.lu().solve_matrix(&p)?;
+		for _ in 0..s {
+			r = r.matmul(&r);
+		}
+		Ok(r)
+	}
+
+	/// Principal square root by the Denman-Beavers iteration. `A` must not
+	/// have eigenvalues on the closed negative real axis.
+	pub fn sqrtm(&self) -> Result<Matrix<T>, LinalgError> {
+		assert!(self.is_square());
+		let n = self.nrows;
+		let half = T::from_f64(0.5);
+		let tol = T::from_f64(n as f64).sqrt() * T::epsilon();
+		let mut y = self.clone();
+		let mut z: Matrix<T> = Matrix::new_identity(n);
+		for _ in 0..100 {
+			let mut y_next = z.inverse()?;
+			y_next += &y;
+			y_next.scalar(half);
+			let mut z_next = y.inverse()?;
+			z_next += &z;
+			z_next.scalar(half);
+
+			let mut diff = y_next.clone();
+			diff -= &y;
+			y = y_next;
+			z = z_next;
+			if diff.norm1() <= tol * y.norm1() {
+				return Ok(y);
+			}
+		}
+		Err(LinalgError::NoConvergence)
+	}
+
+	/// Principal logarithm by inverse scaling and squaring. `A` must not
+	/// have eigenvalues on the closed negative real axis.
+	pub fn logm(&self) -> Result<Matrix<T>, LinalgError> {
+		assert!(self.is_square());
+		let n = self.nrows;
+		let ident: Matrix<T> = Matrix::new_identity(n);
+		let quarter = T::from_f64(0.25);
+
+		// Take square roots until A is close to the identity
+		let mut a = self.clone();
+		let mut k = 0;
+		loop {
+			let mut x = a.clone();
+			x -= &ident;
+			if x.norm1() <= quarter {
+				break;
+			}
+			if k == 64 {
+				return Err(LinalgError::NoConvergence);
+			}
+			a = a.sqrtm()?;
+			k += 1;
+		}
+
+		// log(A) = 2 * atanh(Z) with Z = (A + I)^-1 * (A - I), the factors
+		// commute
+		let mut num = a.clone();
+		num -= &ident;
+		let mut den = a;
+		den += &ident;
+		let z = den.lu().solve_matrix(&num)?;
+
+		let z2 = z.matmul(&z);
+		let mut term = z.clone();
+		let mut result = z;
+		let tol = T::epsilon();
+		let two = T::one() + T::one();
+		let mut j = T::one();
+		for _ in 0..100 {
+			term = term.matmul(&z2);
+			j = j + two;
+			let t = scaled(&term, T::one() / j);
+			result += &t;
+			if t.norm1() <= tol * result.norm1() {
+				break;
+			}
+		}
+		Ok(scaled(&result, T::from_f64(2.0f64.powi(k + 1))))
+	}
+
+	/// Real power `A^p = exp(p * log(A))`.
+	pub fn powm(&self, p: T) -> Result<Matrix<T>, LinalgError> {
+		scaled(&self.logm()?, p).expm()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, assert_close};
+
+	#[test]
+	fn test_pow() {
+		let fib: Matrix<i32> = Matrix::new(2,2, &[1,1,1,0]);
+		assert!(fib.pow(10).equals(&Matrix::new(2,2, &[89,55,55,34])));
+		assert!(fib.pow(0).equals(&Matrix::new_identity(2)));
+		assert!(fib.pow(1).equals(&fib));
+	}
+
+	#[test]
+	fn test_expm() {
+		let z: Matrix<f64> = Matrix::new_zero(3, 3);
+		assert_close(&z.expm().unwrap(), &Matrix::new_identity(3), 0.0);
+
+		let nil: Matrix<f64> = Matrix::new(2,2, &[0.0,1.0,0.0,0.0]);
+		assert_close(&nil.expm().unwrap(), &Matrix::new(2,2, &[1.0,1.0,0.0,1.0]), 1e-15);
+
+		// Large enough to need scaling and squaring
+		let t = 10.0f64;
+		let rot: Matrix<f64> = Matrix::new(2,2, &[0.0,t,-t,0.0]);
+		let expected = Matrix::new(2,2, &[t.cos(),t.sin(),-t.sin(),t.cos()]);
+		assert_close(&rot.expm().unwrap(), &expected, 1e-12);
+
+		let d: Matrix<f64> = Matrix::new(2,2, &[1.0,0.0,0.0,-2.0]);
+		let expected = Matrix::new(2,2, &[1.0f64.exp(),0.0,0.0,(-2.0f64).exp()]);
+		assert_close(&d.expm().unwrap(), &expected, 1e-14);
+	}
+
+	#[test]
+	fn test_sqrtm_logm() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[4.0,1.0,0.0,1.0,5.0,2.0,0.0,1.0,6.0]);
+		let s = a.sqrtm().unwrap();
+		assert_close(&s.matmul(&s), &a, 1e-12);
+
+		let l = a.logm().unwrap();
+		assert_close(&l.expm().unwrap(), &a, 1e-12);
+
+		let b: Matrix<f64> = Matrix::new(2,2, &[0.1,0.2,-0.3,0.05]);
+		assert_close(&b.expm().unwrap().logm().unwrap(), &b, 1e-13);
+	}
+
+	#[test]
+	fn test_powm() {
+		let a: Matrix<f64> = Matrix::new(2,2, &[2.0,1.0,1.0,3.0]);
+		assert_close(&a.powm(0.5).unwrap(), &a.sqrtm().unwrap(), 1e-12);
+		assert_close(&a.powm(3.0).unwrap(), &a.pow(3), 1e-10);
+		assert_close(&a.powm(-1.0).unwrap(), &a.inverse().unwrap(), 1e-12);
+	}
+
+	#[test]
+	fn test_singular() {
+		let a: Matrix<f64> = Matrix::new(2,2, &[1.0,1.0,1.0,1.0]);
+		assert!(a.sqrtm().is_err());
+		assert!(a.logm().is_err());
+	}
+}
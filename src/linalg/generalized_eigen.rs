@@ -0,0 +1,332 @@
+use traits::Float;
+use linalg::{Matrix, LinalgError};
+use linalg::qr::givens;
+
+/// Generalized eigenvalues of a pencil `A - lambda * B`, as pairs
+/// `lambda = (alpha_re + i * alpha_im) / beta`. Only the ratio is
+/// meaningful; `beta` is never negative and is zero for infinite
+/// eigenvalues, which occur when `B` is singular. Complex eigenvalues come
+/// in adjacent conjugate pairs, positive imaginary part first.
+pub struct GeneralizedEigen<T> {
+	alpha_re: Vec<T>,
+	alpha_im: Vec<T>,
+	beta: Vec<T>,
+}
+
+impl<T: Float> Matrix<T> {
+	/// QZ algorithm for `A * x = lambda * B * x` with arbitrary square `A`
+	/// and `B`.
+	pub fn generalized_eigenvalues(&self, b: &Matrix<T>) -> Result<GeneralizedEigen<T>, LinalgError> {
+		assert!(self.is_square() && b.size() == self.size());
+		let n = self.nrows;
+		let (mut h, mut t) = hessenberg_triangular(self, b);
+		let mut alpha_re = vec![T::zero(); n];
+		let mut alpha_im = vec![T::zero(); n];
+		let mut beta = vec![T::zero(); n];
+
+		let eps = T::epsilon();
+		let hnorm = h.norm_frobenius();
+		let tnorm = t.norm_frobenius();
+		let mut iter = 0;
+		let mut since_deflation = 0;
+		let mut end = n;
+		while end > 0 {
+			let hi = end - 1;
+
+			// Look for a single small subdiagonal element
+			let mut lo = hi;
+			while lo > 0 {
+				let s = h[(lo - 1, lo - 1)].abs() + h[(lo, lo)].abs();
+				let s = if s == T::zero() { hnorm } else { s };
+				if h[(lo, lo - 1)].abs() <= eps * s {
+					h[(lo, lo - 1)] = T::zero();
+					break;
+				}
+				lo -= 1;
+			}
+
+			// A zero on the diagonal of T is an infinite eigenvalue, chase it
+			// to the bottom and deflate it there
+			if let Some(k) = (lo..end).find(|&k| t[(k, k)].abs() <= eps * tnorm) {
+				t[(k, k)] = T::zero();
+				for j in k..hi {
+					let (c, s) = givens(t[(j, j + 1)], t[(j + 1, j + 1)]);
+					rotate_rows(&mut h, &mut t, j, c, s);
+					t[(j + 1, j + 1)] = T::zero();
+					if j > lo {
+						let (c, s) = givens(h[(j + 1, j)], h[(j + 1, j - 1)]);
+						rotate_cols(&mut h, &mut t, j - 1, c, s);
+						h[(j + 1, j - 1)] = T::zero();
+					}
+				}
+				if hi > lo {
+					let (c, s) = givens(h[(hi, hi)], h[(hi, hi - 1)]);
+					rotate_cols(&mut h, &mut t, hi - 1, c, s);
+					h[(hi, hi - 1)] = T::zero();
+				}
+				alpha_re[hi] = h[(hi, hi)];
+				beta[hi] = T::zero();
+				end -= 1;
+				since_deflation = 0;
+				continue;
+			}
+
+			if lo == hi {
+				alpha_re[hi] = h[(hi, hi)];
+				beta[hi] = t[(hi, hi)];
+				end -= 1;
+				since_deflation = 0;
+			} else if lo + 1 == hi {
+				block(&h, &t, lo, &mut alpha_re, &mut alpha_im, &mut beta);
+				end -= 2;
+				since_deflation = 0;
+			} else {
+				iter += 1;
+				since_deflation += 1;
+				if iter > 30 * n {
+					return Err(LinalgError::NoConvergence);
+				}
+				qz_step(&mut h, &mut t, lo, hi, since_deflation % 10 == 0);
+			}
+		}
+
+		for k in 0..n {
+			if beta[k] < T::zero() {
+				alpha_re[k] = -alpha_re[k];
+				alpha_im[k] = -alpha_im[k];
+				beta[k] = -beta[k];
+			}
+		}
+		Ok(GeneralizedEigen {
+			alpha_re,
+			alpha_im,
+			beta,
+		})
+	}
+}
+
+impl<T: Float> GeneralizedEigen<T> {
+	pub fn alpha_re(&self) -> &[T] {
+		&self.alpha_re
+	}
+
+	pub fn alpha_im(&self) -> &[T] {
+		&self.alpha_im
+	}
+
+	pub fn beta(&self) -> &[T] {
+		&self.beta
+	}
+
+	pub fn is_infinite(&self, i: usize) -> bool {
+		self.beta[i] == T::zero()
+	}
+}
+
+// Reduces (A, B) to (H, T) with H upper Hessenberg and T upper triangular
+// by orthogonal transformations from both sides.
+fn hessenberg_triangular<T: Float>(a: &Matrix<T>, b: &Matrix<T>) -> (Matrix<T>, Matrix<T>) {
+	let n = a.nrows();
+	let qr = b.qr();
+	let mut qt = qr.q();
+	qt.transpose();
+	let mut h = qt.matmul(a);
+	let mut t = qr.r();
+	for j in 0..n.saturating_sub(2) {
+		for i in ((j + 2)..n).rev() {
+			let (c, s) = givens(h[(i - 1, j)], h[(i, j)]);
+			rotate_rows(&mut h, &mut t, i - 1, c, s);
+			h[(i, j)] = T::zero();
+			let (c, s) = givens(t[(i, i)], t[(i, i - 1)]);
+			rotate_cols(&mut h, &mut t, i - 1, c, s);
+			t[(i, i - 1)] = T::zero();
+		}
+	}
+	(h, t)
+}
+
+// Rows i and i + 1 of H and T times [c s; -s c] from the left.
+fn rotate_rows<T: Float>(h: &mut Matrix<T>, t: &mut Matrix<T>, i: usize, c: T, s: T) {
+	for m in [h, t].iter_mut() {
+		for l in 0..m.ncols() {
+			let (x, y) = (m[(i, l)], m[(i + 1, l)]);
+			m[(i, l)] = c * x + s * y;
+			m[(i + 1, l)] = c * y - s * x;
+		}
+	}
+}
+
+// Columns j and j + 1 of H and T times [c -s; s c] from the right. With `c`
+// and `s` from `givens(m[(r, j + 1)], m[(r, j)])` this zeroes `m[(r, j)]`.
+fn rotate_cols<T: Float>(h: &mut Matrix<T>, t: &mut Matrix<T>, j: usize, c: T, s: T) {
+	for m in [h, t].iter_mut() {
+		for l in 0..m.nrows() {
+			let (x, y) = (m[(l, j)], m[(l, j + 1)]);
+			m[(l, j)] = c * x - s * y;
+			m[(l, j + 1)] = c * y + s * x;
+		}
+	}
+}
+
+// Eigenvalues of the 2x2 pencil at rows and columns k, k + 1, whose T part is
+// nonsingular, from det(H - lambda * T) = 0.
+fn block<T: Float>(h: &Matrix<T>, t: &Matrix<T>, k: usize, re: &mut [T], im: &mut [T], beta: &mut [T]) {
+	let (h11, h12, h21, h22) = (h[(k, k)], h[(k, k + 1)], h[(k + 1, k)], h[(k + 1, k + 1)]);
+	let (t11, t12, t22) = (t[(k, k)], t[(k, k + 1)], t[(k + 1, k + 1)]);
+	let two = T::one() + T::one();
+	let a = t11 * t22;
+	let b = -(h11 * t22 + h22 * t11 - h21 * t12);
+	let c = h11 * h22 - h12 * h21;
+	let disc = b * b - two * two * a * c;
+	beta[k] = T::one();
+	beta[k + 1] = T::one();
+	if disc < T::zero() {
+		re[k] = -b / (two * a);
+		re[k + 1] = re[k];
+		im[k] = (-disc).sqrt() / (two * a).abs();
+		im[k + 1] = -im[k];
+	} else {
+		let root = disc.sqrt();
+		let q = if b < T::zero() { (root - b) / two } else { -(b + root) / two };
+		re[k] = q / a;
+		re[k + 1] = if q == T::zero() { T::zero() } else { c / q };
+	}
+}
+
+// One implicit double shift QZ step on the active block lo..=hi (Moler and
+// Stewart), chasing the bulge with Givens rotations.
+fn qz_step<T: Float>(h: &mut Matrix<T>, t: &mut Matrix<T>, lo: usize, hi: usize, exceptional: bool) {
+	// Shifts: eigenvalues of the trailing 2x2 block of M = H * T^-1, through
+	// their sum s and product p
+	let (s, p) = if exceptional {
+		let w = h[(hi, hi - 1)].abs() / t[(hi - 1, hi - 1)].abs()
+			+ h[(hi - 1, hi - 2)].abs() / t[(hi - 2, hi - 2)].abs();
+		let x = T::from_f64(0.75) * w + h[(hi, hi)] / t[(hi, hi)];
+		(x + x, x * x)
+	} else {
+		let (a, b) = (hi - 1, hi);
+		// Trailing 2x2 of T^-1, with the entry coupling it to column a - 1
+		let u_aa = T::one() / t[(a, a)];
+		let u_bb = T::one() / t[(b, b)];
+		let u_ab = -t[(a, b)] * u_aa * u_bb;
+		let u_pa = -t[(a - 1, a)] / (t[(a - 1, a - 1)] * t[(a, a)]);
+		let u_pb = -(t[(a - 1, a)] * u_ab + t[(a - 1, b)] * u_bb) / t[(a - 1, a - 1)];
+		let m_aa = h[(a, a - 1)] * u_pa + h[(a, a)] * u_aa;
+		let m_ab = h[(a, a - 1)] * u_pb + h[(a, a)] * u_ab + h[(a, b)] * u_bb;
+		let m_ba = h[(b, a)] * u_aa;
+		let m_bb = h[(b, a)] * u_ab + h[(b, b)] * u_bb;
+		(m_aa + m_bb, m_aa * m_bb - m_ab * m_ba)
+	};
+
+	// First column of (M - a1) * (M - a2)
+	let k = lo;
+	let m11 = h[(k, k)] / t[(k, k)];
+	let m21 = h[(k + 1, k)] / t[(k, k)];
+	let m12 = (h[(k, k + 1)] - m11 * t[(k, k + 1)]) / t[(k + 1, k + 1)];
+	let m22 = (h[(k + 1, k + 1)] - m21 * t[(k, k + 1)]) / t[(k + 1, k + 1)];
+	let m32 = h[(k + 2, k + 1)] / t[(k + 1, k + 1)];
+	let mut x = m11 * m11 + m12 * m21 - s * m11 + p;
+	let mut y = m21 * (m11 + m22 - s);
+	let mut z = m21 * m32;
+
+	for k in lo..(hi - 1) {
+		// Zero z and y from the left
+		let (c, sn) = givens(y, z);
+		rotate_rows(h, t, k + 1, c, sn);
+		let (c, sn) = givens(x, y.hypot(z));
+		rotate_rows(h, t, k, c, sn);
+		if k > lo {
+			h[(k + 1, k - 1)] = T::zero();
+			h[(k + 2, k - 1)] = T::zero();
+		}
+
+		// Restore T to triangular form from the right
+		let (c, sn) = givens(t[(k + 2, k + 1)], t[(k + 2, k)]);
+		rotate_cols(h, t, k, c, sn);
+		t[(k + 2, k)] = T::zero();
+		let (c, sn) = givens(t[(k + 2, k + 2)], t[(k + 2, k + 1)]);
+		rotate_cols(h, t, k + 1, c, sn);
+		t[(k + 2, k + 1)] = T::zero();
+		let (c, sn) = givens(t[(k + 1, k + 1)], t[(k + 1, k)]);
+		rotate_cols(h, t, k, c, sn);
+		t[(k + 1, k)] = T::zero();
+
+		x = h[(k + 1, k)];
+		y = h[(k + 2, k)];
+		if k + 3 <= hi {
+			z = h[(k + 3, k)];
+		}
+	}
+
+	// Final 2x2 rotation
+	let k = hi - 1;
+	let (c, sn) = givens(x, y);
+	rotate_rows(h, t, k, c, sn);
+	h[(k + 1, k - 1)] = T::zero();
+	let (c, sn) = givens(t[(k + 1, k + 1)], t[(k + 1, k)]);
+	rotate_cols(h, t, k, c, sn);
+	t[(k + 1, k)] = T::zero();
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::Matrix;
+
+	// Sorted (re, im) pairs of the finite eigenvalues
+	fn finite(a: &Matrix<f64>, b: &Matrix<f64>) -> Vec<(f64, f64)> {
+		let eig = a.generalized_eigenvalues(b).unwrap();
+		let mut v: Vec<(f64, f64)> = (0..eig.beta().len())
+			.filter(|&i| !eig.is_infinite(i))
+			.map(|i| (eig.alpha_re()[i] / eig.beta()[i], eig.alpha_im()[i] / eig.beta()[i]))
+			.collect();
+		v.sort_by(|x, y| x.partial_cmp(y).unwrap());
+		v
+	}
+
+	#[test]
+	fn test_qz_matches_standard_problem() {
+		let a: Matrix<f64> = Matrix::new(4,4, &[
+			1.0,2.0,3.0,4.0,
+			-5.0,6.0,7.0,8.0,
+			9.0,-10.0,11.0,12.0,
+			13.0,14.0,-15.0,16.0]);
+		let b: Matrix<f64> = Matrix::new(4,4, &[
+			2.0,1.0,0.0,0.0,
+			0.0,3.0,1.0,0.0,
+			1.0,0.0,4.0,1.0,
+			0.0,1.0,0.0,5.0]);
+		// B^-1 * A has the same eigenvalues
+		let eig = b.inverse().unwrap().matmul(&a).eigen().unwrap();
+		let mut expected: Vec<(f64, f64)> = eig.real().iter().cloned().zip(eig.imag().iter().cloned()).collect();
+		expected.sort_by(|x, y| x.partial_cmp(y).unwrap());
+		let values = finite(&a, &b);
+		assert_eq!(values.len(), 4);
+		for (x, e) in values.iter().zip(expected.iter()) {
+			assert!((x.0 - e.0).abs() < 1e-9 && (x.1 - e.1).abs() < 1e-9);
+		}
+		assert!(values.iter().any(|x| x.1 != 0.0));
+	}
+
+	#[test]
+	fn test_qz_infinite() {
+		// det(A - lambda * B) = -2 - 4 * lambda
+		let a: Matrix<f64> = Matrix::new(2,2, &[1.0,2.0,3.0,4.0]);
+		let b: Matrix<f64> = Matrix::new(2,2, &[1.0,0.0,0.0,0.0]);
+		let eig = a.generalized_eigenvalues(&b).unwrap();
+		assert_eq!((0..2).filter(|&i| eig.is_infinite(i)).count(), 1);
+		let values = finite(&a, &b);
+		assert_eq!(values.len(), 1);
+		assert!((values[0].0 + 0.5).abs() < 1e-12);
+
+		// Singular B inside a larger pencil: diag(1, 2, 3) against diag(1, 0, 1)
+		let mut a: Matrix<f64> = Matrix::new(3,3, &[1.0,1.0,0.0,0.0,2.0,1.0,0.0,0.0,3.0]);
+		let b: Matrix<f64> = Matrix::new(3,3, &[1.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,1.0]);
+		let values = finite(&a, &b);
+		assert_eq!(values.len(), 2);
+		assert!((values[0].0 - 1.0).abs() < 1e-12 && (values[1].0 - 3.0).abs() < 1e-12);
+
+		a[(2, 0)] = 1.0;
+		let eig = a.generalized_eigenvalues(&b).unwrap();
+		assert_eq!((0..3).filter(|&i| eig.is_infinite(i)).count(), 1);
+	}
+}
@@ -0,0 +1,145 @@
+use traits::Float;
+use linalg::Matrix;
+
+/// Orthogonal reduction to upper Hessenberg form, `A = Q * H * Q^T`.
+pub struct Hessenberg<T> {
+	h: Matrix<T>,
+	q: Matrix<T>,
+}
+
+impl<T: Float> Matrix<T> {
+	pub fn hessenberg(&self) -> Hessenberg<T> {
+		assert!(self.is_square());
+		let mut h = self.clone();
+		let mut q = Matrix::new_zero(self.nrows, self.nrows);
+		orthes(&mut h, &mut q);
+		Hessenberg {
+			h,
+			q,
+		}
+	}
+}
+
+impl<T: Float> Hessenberg<T> {
+	pub fn h(&self) -> Matrix<T> {
+		self.h.clone()
+	}
+
+	pub fn q(&self) -> Matrix<T> {
+		self.q.clone()
+	}
+
+	pub(crate) fn into_parts(self) -> (Matrix<T>, Matrix<T>) {
+		(self.h, self.q)
+	}
+}
+
+// Householder reduction to Hessenberg form (EISPACK orthes/ortran). `h` is
+// reduced in place and `v` receives the accumulated transformations.
+fn orthes<T: Float>(h: &mut Matrix<T>, v: &mut Matrix<T>) {
+	let n = h.nrows();
+	let mut ort = vec![T::zero(); n];
+	for m in 1..n.saturating_sub(1) {
+		let mut scale = T::zero();
+		for i in m..n {
+			scale = scale + h[(i, m - 1)].abs();
+		}
+		if scale == T::zero() {
+			continue;
+		}
+
+		let mut hh = T::zero();
+		for i in (m..n).rev() {
+			ort[i] = h[(i, m - 1)] / scale;
+			hh = hh + ort[i] * ort[i];
+		}
+		let mut g = hh.sqrt();
+		if ort[m] > T::zero() {
+			g = -g;
+		}
+		hh = hh - ort[m] * g;
+		ort[m] = ort[m] - g;
+
+		// H = (I - u * u^T / h) * H * (I - u * u^T / h)
+		for j in m..n {
+			let mut f = T::zero();
+			for i in (m..n).rev() {
+				f = f + ort[i] * h[(i, j)];
+			}
+			f = f / hh;
+			for i in m..n {
+				h[(i, j)] = h[(i, j)] - f * ort[i];
+			}
+		}
+		for i in 0..n {
+			let mut f = T::zero();
+			for j in (m..n).rev() {
+				f = f + ort[j] * h[(i, j)];
+			}
+			f = f / hh;
+			for j in m..n {
+				h[(i, j)] = h[(i, j)] - f * ort[j];
+			}
+		}
+		ort[m] = scale * ort[m];
+		h[(m, m - 1)] = scale * g;
+	}
+
+	for i in 0..n {
+		for j in 0..n {
+			v[(i, j)] = if i == j { T::one() } else { T::zero() };
+		}
+	}
+	for m in (1..n.saturating_sub(1)).rev() {
+		if h[(m, m - 1)] == T::zero() {
+			continue;
+		}
+		for i in (m + 1)..n {
+			ort[i] = h[(i, m - 1)];
+		}
+		for j in m..n {
+			let mut g = T::zero();
+			for i in m..n {
+				g = g + ort[i] * v[(i, j)];
+			}
+			// Double division avoids possible underflow
+			g = (g / ort[m]) / h[(m, m - 1)];
+			for i in m..n {
+				v[(i, j)] = v[(i, j)] + g * ort[i];
+			}
+		}
+	}
+
+	// Drop the Householder vectors stored below the subdiagonal
+	for i in 2..n {
+		for j in 0..(i - 1) {
+			h[(i, j)] = T::zero();
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, assert_close};
+
+	#[test]
+	fn test_hessenberg() {
+		let a: Matrix<f64> = Matrix::new(4,4, &[
+			4.0,1.0,-2.0,2.0,
+			3.0,2.0,0.0,1.0,
+			-2.0,5.0,3.0,-2.0,
+			7.0,1.0,-2.0,-1.0]);
+		let hess = a.hessenberg();
+		let h = hess.h();
+		for i in 2..4 {
+			for j in 0..(i - 1) {
+				assert_eq!(h[(i, j)], 0.0);
+			}
+		}
+		let q = hess.q();
+		let mut qt = q.clone();
+		qt.transpose();
+		assert_close(&qt.matmul(&q), &Matrix::new_identity(4), 1e-12);
+		assert_close(&q.matmul(&h).matmul(&qt), &a, 1e-12);
+	}
+}
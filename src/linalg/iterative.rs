@@ -0,0 +1,507 @@
+//! Krylov subspace solvers for `A * x = b`.
+//!
+//! The operator only needs to provide matrix-vector products, so the
+//! solvers run against a dense `Matrix` as well as any closure of the form
+//! `|x: &[T], y: &mut [T]| { /* y = A * x */ }`.
+
+use traits::Float;
+use linalg::{Matrix, Vector};
+
+pub trait LinearOperator<T> {
+	/// Computes `y = A * x`.
+	fn apply(&self, x: &[T], y: &mut [T]);
+}
+
+impl<T: Float> LinearOperator<T> for Matrix<T> {
+	fn apply(&self, x: &[T], y: &mut [T]) {
+		assert!(self.is_square() && x.len() == self.ncols && y.len() == self.nrows);
+		for (i, yi) in y.iter_mut().enumerate() {
+			let mut s = T::zero();
+			for (j, &xj) in x.iter().enumerate() {
+				s = s + self[(i, j)] * xj;
+			}
+			*yi = s;
+		}
+	}
+}
+
+impl<T, F: Fn(&[T], &mut [T])> LinearOperator<T> for F {
+	fn apply(&self, x: &[T], y: &mut [T]) {
+		self(x, y)
+	}
+}
+
+pub struct SolverConfig<T> {
+	/// Stop once `|b - A * x| <= tolerance * |b|`.
+	pub tolerance: T,
+	/// Each iteration is one product with `A`, two for BiCGSTAB.
+	pub max_iterations: usize,
+	/// Krylov subspace dimension before GMRES restarts.
+	pub restart: usize,
+}
+
+impl<T: Float> SolverConfig<T> {
+	pub fn new(tolerance: T, max_iterations: usize) -> Self {
+		SolverConfig {
+			tolerance,
+			max_iterations,
+			restart: 30,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopReason {
+	Converged,
+	MaxIterations,
+	/// A scalar the method divides by vanished, e.g. `A` is not positive
+	/// definite for CG or is singular.
+	Breakdown,
+}
+
+pub struct Solution<T> {
+	x: Vector<T>,
+	iterations: usize,
+	residuals: Vec<T>,
+	reason: StopReason,
+}
+
+impl<T> Solution<T> {
+	pub fn x(&self) -> &Vector<T> {
+		&self.x
+	}
+
+	pub fn into_x(self) -> Vector<T> {
+		self.x
+	}
+
+	pub fn iterations(&self) -> usize {
+		self.iterations
+	}
+
+	/// Relative residual norms `|b - A * x| / |b|`, starting with the one of
+	/// the initial guess.
+	pub fn residuals(&self) -> &[T] {
+		&self.residuals
+	}
+
+	pub fn reason(&self) -> StopReason {
+		self.reason
+	}
+
+	pub fn converged(&self) -> bool {
+		self.reason == StopReason::Converged
+	}
+}
+
+pub(crate) fn dot<T: Float>(x: &[T], y: &[T]) -> T {
+	x.iter().zip(y.iter()).fold(T::zero(), |s, (&a, &b)| s + a * b)
+}
+
+pub(crate) fn norm<T: Float>(x: &[T]) -> T {
+	dot(x, x).sqrt()
+}
+
+// y = y + a * x
+pub(crate) fn axpy<T: Float>(a: T, x: &[T], y: &mut [T]) {
+	for (yi, &xi) in y.iter_mut().zip(x.iter()) {
+		*yi = *yi + a * xi;
+	}
+}
+
+// Returns the initial guess, its residual and the norm of `b`.
+fn setup<T: Float, A: LinearOperator<T>>(a: &A, b: &Vector<T>, x0: Option<&Vector<T>>)
+	-> (Vec<T>, Vec<T>, T) {
+	let n = b.size();
+	let x = match x0 {
+		Some(x0) => {
+			assert!(x0.size() == n);
+			x0.v.clone()
+		}
+		None => vec![T::zero(); n],
+	};
+	let mut r = vec![T::zero(); n];
+	a.apply(&x, &mut r);
+	for (ri, &bi) in r.iter_mut().zip(b.v.iter()) {
+		*ri = bi - *ri;
+	}
+	let bnorm = norm(&b.v);
+	(x, r, if bnorm == T::zero() { T::one() } else { bnorm })
+}
+
+// `residuals` holds the initial residual and one entry per completed
+// iteration, whatever the reason for stopping.
+fn finish<T>(x: Vec<T>, residuals: Vec<T>, reason: StopReason) -> Solution<T> {
+	Solution {
+		x: Vector { v: x },
+		iterations: residuals.len() - 1,
+		residuals,
+		reason,
+	}
+}
+
+/// Conjugate gradient, for symmetric positive definite `A`.
+pub fn cg<T, A>(a: &A, b: &Vector<T>, x0: Option<&Vector<T>>, config: &SolverConfig<T>) -> Solution<T>
+	where T: Float, A: LinearOperator<T> {
+	let (mut x, mut r, bnorm) = setup(a, b, x0);
+	let mut rr = dot(&r, &r);
+	let mut residuals = vec![rr.sqrt() / bnorm];
+	if residuals[0] <= config.tolerance {
+		return finish(x, residuals, StopReason::Converged);
+	}
+
+	let mut p = r.clone();
+	let mut ap = vec![T::zero(); b.size()];
+	for _ in 0..config.max_iterations {
+		a.apply(&p, &mut ap);
+		let pap = dot(&p, &ap);
+		if pap <= T::zero() {
+			return finish(x, residuals, StopReason::Breakdown);
+		}
+		let alpha = rr / pap;
+		axpy(alpha, &p, &mut x);
+		axpy(-alpha, &ap, &mut r);
+		let rr_new = dot(&r, &r);
+		residuals.push(rr_new.sqrt() / bnorm);
+		if rr_new.sqrt() / bnorm <= config.tolerance {
+			return finish(x, residuals, StopReason::Converged);
+		}
+		let beta = rr_new / rr;
+		for (pi, &ri) in p.iter_mut().zip(r.iter()) {
+			*pi = ri + beta * *pi;
+		}
+		rr = rr_new;
+	}
+	finish(x, residuals, StopReason::MaxIterations)
+}
+
+/// MINRES, for symmetric and possibly indefinite `A`.
+pub fn minres<T, A>(a: &A, b: &Vector<T>, x0: Option<&Vector<T>>, config: &SolverConfig<T>) -> Solution<T>
+	where T: Float, A: LinearOperator<T> {
+	let n = b.size();
+	let (mut x, r, bnorm) = setup(a, b, x0);
+	let beta1 = norm(&r);
+	let mut residuals = vec![beta1 / bnorm];
+	if residuals[0] <= config.tolerance {
+		return finish(x, residuals, StopReason::Converged);
+	}
+
+	// Lanczos vectors v_{j-1}, v_j and search directions w_{j-2}, w_{j-1}
+	let mut v_old = vec![T::zero(); n];
+	let mut v: Vec<T> = r.iter().map(|&ri| ri / beta1).collect();
+	let mut w_old2 = vec![T::zero(); n];
+	let mut w_old = vec![T::zero(); n];
+	let mut av = vec![T::zero(); n];
+	let mut beta = T::zero();
+	// Givens rotations G_{j-2} and G_{j-1}
+	let (mut c_old, mut s_old) = (T::one(), T::zero());
+	let (mut c, mut s) = (T::one(), T::zero());
+	let mut eta = beta1;
+
+	for _ in 0..config.max_iterations {
+		a.apply(&v, &mut av);
+		let alpha = dot(&v, &av);
+		for i in 0..n {
+			av[i] = av[i] - alpha * v[i] - beta * v_old[i];
+		}
+		let beta_new = norm(&av);
+
+		// Apply the previous rotations to the new column of T
+		let epsilon = s_old * beta;
+		let delta_bar = c_old * beta;
+		let delta = c * delta_bar + s * alpha;
+		let gamma_bar = c * alpha - s * delta_bar;
+		let gamma = gamma_bar.hypot(beta_new);
+		if gamma == T::zero() {
+			return finish(x, residuals, StopReason::Breakdown);
+		}
+		c_old = c;
+		s_old = s;
+		c = gamma_bar / gamma;
+		s = beta_new / gamma;
+
+		let mut w = vec![T::zero(); n];
+		for i in 0..n {
+			w[i] = (v[i] - delta * w_old[i] - epsilon * w_old2[i]) / gamma;
+		}
+		axpy(c * eta, &w, &mut x);
+		eta = -s * eta;
+		residuals.push(eta.abs() / bnorm);
+		if eta.abs() / bnorm <= config.tolerance || beta_new == T::zero() {
+			return finish(x, residuals, StopReason::Converged);
+		}
+
+		w_old2 = w_old;
+		w_old = w;
+		v_old = v;
+		v = av.iter().map(|&x| x / beta_new).collect();
+		beta = beta_new;
+	}
+	finish(x, residuals, StopReason::MaxIterations)
+}
+
+/// Restarted GMRES(m) with `m = config.restart`, for general `A`.
+pub fn gmres<T, A>(a: &A, b: &Vector<T>, x0: Option<&Vector<T>>, config: &SolverConfig<T>) -> Solution<T>
+	where T: Float, A: LinearOperator<T> {
+	let n = b.size();
+	let m = ::std::cmp::max(1, ::std::cmp::min(config.restart, n));
+	let (mut x, mut r, bnorm) = setup(a, b, x0);
+	let mut residuals = vec![norm(&r) / bnorm];
+	if residuals[0] <= config.tolerance {
+		return finish(x, residuals, StopReason::Converged);
+	}
+
+	let mut iterations = 0;
+	loop {
+		let beta = norm(&r);
+		let mut basis: Vec<Vec<T>> = vec![r.iter().map(|&ri| ri / beta).collect()];
+		let mut h: Matrix<T> = Matrix::new_zero(m + 1, m);
+		let mut cs = vec![T::zero(); m];
+		let mut sn = vec![T::zero(); m];
+		let mut g = vec![T::zero(); m + 1];
+		g[0] = beta;
+
+		let mut k = 0;
+		let mut reason = None;
+		while k < m {
+			let mut w = vec![T::zero(); n];
+			a.apply(&basis[k], &mut w);
+			iterations += 1;
+
+			// Modified Gram-Schmidt
+			for (i, vi) in basis.iter().enumerate() {
+				let hik = dot(&w, vi);
+				h[(i, k)] = hik;
+				axpy(-hik, vi, &mut w);
+			}
+			let hnext = norm(&w);
+
+			for i in 0..k {
+				let t = cs[i] * h[(i, k)] + sn[i] * h[(i + 1, k)];
+				h[(i + 1, k)] = cs[i] * h[(i + 1, k)] - sn[i] * h[(i, k)];
+				h[(i, k)] = t;
+			}
+			let d = h[(k, k)].hypot(hnext);
+			if d == T::zero() {
+				reason = Some(StopReason::Breakdown);
+				break;
+			}
+			cs[k] = h[(k, k)] / d;
+			sn[k] = hnext / d;
+			h[(k, k)] = d;
+			g[k + 1] = -sn[k] * g[k];
+			g[k] = cs[k] * g[k];
+			k += 1;
+
+			let res = g[k].abs() / bnorm;
+			residuals.push(res);
+			if res <= config.tolerance || hnext == T::zero() {
+				reason = Some(StopReason::Converged);
+				break;
+			}
+			if iterations >= config.max_iterations {
+				reason = Some(StopReason::MaxIterations);
+				break;
+			}
+			basis.push(w.iter().map(|&wi| wi / hnext).collect());
+		}
+
+		// x = x + V * y with H * y = g
+		let mut y = vec![T::zero(); k];
+		for i in (0..k).rev() {
+			let mut s = g[i];
+			for j in (i + 1)..k {
+				s = s - h[(i, j)] * y[j];
+			}
+			y[i] = s / h[(i, i)];
+		}
+		for (yi, vi) in y.iter().zip(basis.iter()) {
+			axpy(*yi, vi, &mut x);
+		}
+
+		if let Some(reason) = reason {
+			return finish(x, residuals, reason);
+		}
+		if iterations >= config.max_iterations {
+			return finish(x, residuals, StopReason::MaxIterations);
+		}
+		a.apply(&x, &mut r);
+		for (ri, &bi) in r.iter_mut().zip(b.v.iter()) {
+			*ri = bi - *ri;
+		}
+	}
+}
+
+/// BiCGSTAB, for general `A`.
+pub fn bicgstab<T, A>(a: &A, b: &Vector<T>, x0: Option<&Vector<T>>, config: &SolverConfig<T>) -> Solution<T>
+	where T: Float, A: LinearOperator<T> {
+	let n = b.size();
+	let (mut x, mut r, bnorm) = setup(a, b, x0);
+	let mut residuals = vec![norm(&r) / bnorm];
+	if residuals[0] <= config.tolerance {
+		return finish(x, residuals, StopReason::Converged);
+	}
+
+	let r_hat = r.clone();
+	let (mut rho, mut alpha, mut omega) = (T::one(), T::one(), T::one());
+	let mut p = vec![T::zero(); n];
+	let mut v = vec![T::zero(); n];
+	let mut s = vec![T::zero(); n];
+	let mut t = vec![T::zero(); n];
+	for _ in 0..config.max_iterations {
+		let rho_new = dot(&r_hat, &r);
+		if rho_new == T::zero() {
+			return finish(x, residuals, StopReason::Breakdown);
+		}
+		let beta = (rho_new / rho) * (alpha / omega);
+		for i in 0..n {
+			p[i] = r[i] + beta * (p[i] - omega * v[i]);
+		}
+		a.apply(&p, &mut v);
+		let rv = dot(&r_hat, &v);
+		if rv == T::zero() {
+			return finish(x, residuals, StopReason::Breakdown);
+		}
+		alpha = rho_new / rv;
+		for i in 0..n {
+			s[i] = r[i] - alpha * v[i];
+		}
+		let snorm = norm(&s) / bnorm;
+		if snorm <= config.tolerance {
+			axpy(alpha, &p, &mut x);
+			residuals.push(snorm);
+			return finish(x, residuals, StopReason::Converged);
+		}
+
+		a.apply(&s, &mut t);
+		let tt = dot(&t, &t);
+		omega = if tt == T::zero() { T::zero() } else { dot(&t, &s) / tt };
+		axpy(alpha, &p, &mut x);
+		axpy(omega, &s, &mut x);
+		for i in 0..n {
+			r[i] = s[i] - omega * t[i];
+		}
+		let res = norm(&r) / bnorm;
+		residuals.push(res);
+		if res <= config.tolerance {
+			return finish(x, residuals, StopReason::Converged);
+		}
+		if omega == T::zero() {
+			return finish(x, residuals, StopReason::Breakdown);
+		}
+		rho = rho_new;
+	}
+	finish(x, residuals, StopReason::MaxIterations)
+}
+
+// 1D Laplacian, symmetric positive definite. Shared with the eigensolver tests.
+#[cfg(test)]
+pub(crate) fn laplacian(n: usize) -> Matrix<f64> {
+	let mut a = Matrix::new_zero(n, n);
+	for i in 0..n {
+		a[(i, i)] = 2.0;
+		if i > 0 {
+			a[(i, i - 1)] = -1.0;
+			a[(i - 1, i)] = -1.0;
+		}
+	}
+	a
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, Vector};
+	use super::*;
+
+	fn check(a: &Matrix<f64>, b: &Vector<f64>, sol: &Solution<f64>) {
+		assert!(sol.converged(), "{:?}", sol.reason());
+		assert_eq!(sol.residuals().len(), sol.iterations() + 1);
+		let mut ax = vec![0.0; b.size()];
+		a.apply(&sol.x().iter().cloned().collect::<Vec<_>>(), &mut ax);
+		for (i, y) in ax.iter().enumerate() {
+			assert!((y - b[i]).abs() < 1e-8);
+		}
+	}
+
+	#[test]
+	fn test_cg() {
+		let a = laplacian(20);
+		let b = Vector::new(&[1.0; 20]);
+		let config = SolverConfig::new(1e-12, 100);
+		let sol = cg(&a, &b, None, &config);
+		check(&a, &b, &sol);
+		// Exact arithmetic would need at most n steps
+		assert!(sol.iterations() <= 20);
+
+		// Not positive definite
+		let c: Matrix<f64> = Matrix::new(2,2, &[1.0,0.0,0.0,-1.0]);
+		let sol = cg(&c, &Vector::new(&[0.0,1.0]), None, &config);
+		assert_eq!(sol.reason(), StopReason::Breakdown);
+		assert_eq!(sol.iterations(), 0);
+		assert_eq!(sol.residuals().len(), 1);
+	}
+
+	#[test]
+	fn test_bicgstab_breakdown() {
+		// Skew-symmetric, so r . A r = 0 on the first step
+		let a: Matrix<f64> = Matrix::new(2,2, &[0.0,1.0,-1.0,0.0]);
+		let sol = bicgstab(&a, &Vector::new(&[1.0,0.0]), None, &SolverConfig::new(1e-12, 10));
+		assert_eq!(sol.reason(), StopReason::Breakdown);
+		assert_eq!(sol.residuals().len(), sol.iterations() + 1);
+		assert_eq!(sol.iterations(), 0);
+	}
+
+	#[test]
+	fn test_minres_indefinite() {
+		let mut a = laplacian(10);
+		for i in 0..10 {
+			a[(i, i)] = if i % 2 == 0 { 3.0 } else { -3.0 };
+		}
+		let b = Vector::new(&[1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0,9.0,10.0]);
+		let sol = minres(&a, &b, None, &SolverConfig::new(1e-12, 100));
+		check(&a, &b, &sol);
+	}
+
+	#[test]
+	fn test_gmres_and_bicgstab() {
+		let n = 15;
+		let mut a = laplacian(n);
+		for i in 0..n {
+			a[(i, i)] = 4.0;
+			if i > 0 {
+				a[(i, i - 1)] = -1.5;
+			}
+		}
+		let b = Vector::new(&(0..n).map(|i| i as f64).collect::<Vec<_>>());
+		let mut config = SolverConfig::new(1e-12, 500);
+		config.restart = 5;
+		let sol = gmres(&a, &b, None, &config);
+		check(&a, &b, &sol);
+		let sol = bicgstab(&a, &b, None, &config);
+		check(&a, &b, &sol);
+	}
+
+	#[test]
+	fn test_closure_operator() {
+		let n = 30;
+		let op = |x: &[f64], y: &mut [f64]| {
+			for i in 0..x.len() {
+				y[i] = 2.0 * x[i];
+				if i > 0 {
+					y[i] -= x[i - 1];
+				}
+				if i + 1 < x.len() {
+					y[i] -= x[i + 1];
+				}
+			}
+		};
+		let b = Vector::new(&vec![1.0; n]);
+		let x0 = Vector::new(&vec![1.0; n]);
+		let sol = cg(&op, &b, Some(&x0), &SolverConfig::new(1e-10, 200));
+		check(&laplacian(n), &b, &sol);
+
+		let sol = gmres(&op, &b, None, &SolverConfig::new(1e-10, 3));
+		assert_eq!(sol.reason(), StopReason::MaxIterations);
+		assert_eq!(sol.iterations(), 3);
+	}
+}
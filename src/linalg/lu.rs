@@ -0,0 +1,236 @@
+use traits::Float;
+use linalg::{Matrix, Vector, LinalgError};
+
+/// LU factorization with partial pivoting, `P * A = L * U`.
+pub struct Lu<T> {
+	lu: Matrix<T>,
+	perm: Vec<usize>,
+	sign: T,
+	singular: bool,
+}
+
+impl<T: Float> Matrix<T> {
+	/// Pivots at most `n * eps * max|a_ij|` in magnitude are treated as zero,
+	/// so numerically singular matrices are reported as singular.
+	pub fn lu(&self) -> Lu<T> {
+		assert!(self.is_square());
+		let n = self.nrows;
+		let scale = self.iter().fold(T::zero(), |m, &x| if x.abs() > m { x.abs() } else { m });
+		let tol = T::from_f64(n as f64) * T::epsilon() * scale;
+		let mut lu = self.clone();
+		let mut perm: Vec<usize> = (0..n).collect();
+		let mut sign = T::one();
+		let mut singular = false;
+		for k in 0..n {
+			let mut p = k;
+			let mut max = lu[(k, k)].abs();
+			for i in (k + 1)..n {
+				let a = lu[(i, k)].abs();
+				if a > max {
+					max = a;
+					p = i;
+				}
+			}
+			if p != k {
+				lu.swap_rows(p, k);
+				perm.swap(p, k);
+				sign = -sign;
+			}
+			let pivot = lu[(k, k)];
+			if pivot.abs() <= tol {
+				singular = true;
+				continue;
+			}
+			for i in (k + 1)..n {
+				let l = lu[(i, k)] / pivot;
+				lu[(i, k)] = l;
+				for j in (k + 1)..n {
+					lu[(i, j)] = lu[(i, j)] - l * lu[(k, j)];
+				}
+			}
+		}
+		Lu {
+			lu,
+			perm,
+			sign,
+			singular,
+		}
+	}
+
+	pub fn determinant(&self) -> T {
+		self.lu().determinant()
+	}
+
+	pub fn inverse(&self) -> Result<Matrix<T>, LinalgError> {
+		self.lu().inverse()
+	}
+}
+
+impl<T: Float> Lu<T> {
+	pub fn l(&self) -> Matrix<T> {
+		let n = self.lu.nrows;
+		let mut l = Matrix::new_identity(n);
+		for i in 0..n {
+			for j in 0..i {
+				l[(i, j)] = self.lu[(i, j)];
+			}
+		}
+		l
+	}
+
+	pub fn u(&self) -> Matrix<T> {
+		let n = self.lu.nrows;
+		let mut u = Matrix::new_zero(n, n);
+		for i in 0..n {
+			for j in i..n {
+				u[(i, j)] = self.lu[(i, j)];
+			}
+		}
+		u
+	}
+
+	/// The permutation matrix `P`.
+	pub fn p(&self) -> Matrix<T> {
+		let n = self.lu.nrows;
+		let mut p = Matrix::new_zero(n, n);
+		for (i, &j) in self.perm.iter().enumerate() {
+			p[(i, j)] = T::one();
+		}
+		p
+	}
+
+	/// Row `i` of `P * A` is row `permutation()[i]` of `A`.
+	pub fn permutation(&self) -> &[usize] {
+		&self.perm
+	}
+
+	/// The sign of the permutation, `1` or `-1`.
+	pub fn sign(&self) -> T {
+		self.sign
+	}
+
+	pub fn is_singular(&self) -> bool {
+		self.singular
+	}
+
+	pub fn determinant(&self) -> T {
+		let mut d = self.sign;
+		for i in 0..self.lu.nrows {
+			d = d * self.lu[(i, i)];
+		}
+		d
+	}
+
+	pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, LinalgError> {
+		let n = self.lu.nrows;
+		assert!(b.size() == n);
+		let mut x = Matrix::new_zero(n, 1);
+		for i in 0..n {
+			x[(i, 0)] = b[self.perm[i]];
+		}
+		self.substitute(&mut x)?;
+		Ok(Vector { v: x.v })
+	}
+
+	pub fn solve_matrix(&self, b: &Matrix<T>) -> Result<Matrix<T>, LinalgError> {
+		let n = self.lu.nrows;
+		assert!(b.nrows() == n);
+		let mut x = Matrix::new_zero(n, b.ncols());
+		for i in 0..n {
+			for j in 0..b.ncols() {
+				x[(i, j)] = b[(self.perm[i], j)];
+			}
+		}
+		self.substitute(&mut x)?;
+		Ok(x)
+	}
+
+	pub fn inverse(&self) -> Result<Matrix<T>, LinalgError> {
+		let n = self.lu.nrows;
+		self.solve_matrix(&Matrix::new_identity(n))
+	}
+
+	// Forward and back substitution on an already permuted right-hand side.
+	fn substitute(&self, x: &mut Matrix<T>) -> Result<(), LinalgError> {
+		if self.singular {
+			return Err(LinalgError::Singular);
+		}
+		let n = self.lu.nrows;
+		for j in 0..x.ncols() {
+			for i in 0..n {
+				let mut s = x[(i, j)];
+				for k in 0..i {
+					s = s - self.lu[(i, k)] * x[(k, j)];
+				}
+				x[(i, j)] = s;
+			}
+			for i in (0..n).rev() {
+				let mut s = x[(i, j)];
+				for k in (i + 1)..n {
+					s = s - self.lu[(i, k)] * x[(k, j)];
+				}
+				x[(i, j)] = s / self.lu[(i, i)];
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, Vector, LinalgError, assert_close};
+
+	#[test]
+	fn test_lu_reconstruct() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[2.0,1.0,1.0,4.0,-6.0,0.0,-2.0,7.0,2.0]);
+		let lu = a.lu();
+		let pa = lu.p().matmul(&a);
+		let prod = lu.l().matmul(&lu.u());
+		assert_close(&pa, &prod, 1e-12);
+		assert!(!lu.is_singular());
+	}
+
+	#[test]
+	fn test_determinant() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[2.0,1.0,1.0,4.0,-6.0,0.0,-2.0,7.0,2.0]);
+		assert!((a.determinant() - (-16.0)).abs() < 1e-12);
+		let b: Matrix<f64> = Matrix::new(2,2, &[0.0,1.0,1.0,0.0]);
+		assert_eq!(b.lu().sign(), -1.0);
+		assert_eq!(b.determinant(), -1.0);
+	}
+
+	#[test]
+	fn test_solve() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[2.0,1.0,1.0,4.0,-6.0,0.0,-2.0,7.0,2.0]);
+		let b = Vector::new(&[5.0,-2.0,9.0]);
+		let x = a.lu().solve(&b).unwrap();
+		let expected = [1.0, 1.0, 2.0];
+		for i in 0..3 {
+			assert!((x[i] - expected[i]).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn test_inverse() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[4.0,7.0,2.0,3.0,6.0,1.0,2.0,5.0,3.0]);
+		let inv = a.inverse().unwrap();
+		assert_close(&a.matmul(&inv), &Matrix::new_identity(3), 1e-12);
+	}
+
+	#[test]
+	fn test_singular() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[1.0,2.0,3.0,2.0,4.0,6.0,1.0,0.0,1.0]);
+		let lu = a.lu();
+		assert!(lu.is_singular());
+		assert_eq!(lu.determinant(), 0.0);
+		assert!(lu.solve(&Vector::new(&[1.0,2.0,3.0])).err() == Some(LinalgError::Singular));
+		assert!(a.inverse().is_err());
+
+		// Rank 2, but the last pivot is rounding noise rather than zero
+		let a: Matrix<f64> = Matrix::new(3,3, &[1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0,9.0]);
+		let lu = a.lu();
+		assert!(lu.is_singular());
+		assert!(lu.solve(&Vector::new(&[1.0,2.0,3.0])).err() == Some(LinalgError::Singular));
+		assert!(a.inverse().is_err());
+	}
+}
@@ -0,0 +1,141 @@
+use linalg::{Matrix, Vector, LinalgError};
+
+const MAX_REFINEMENTS: usize = 30;
+
+/// Result of `solve_mixed`.
+pub struct MixedSolution {
+	x: Vector<f64>,
+	iterations: usize,
+	backward_error: f64,
+	error_estimate: f64,
+}
+
+impl Matrix<f64> {
+	/// Solves `A * x = b` to double precision while doing the `O(n^3)` LU
+	/// factorization in single precision. Residuals are computed in `f64` and
+	/// the correction is solved with the `f32` factors until the normwise
+	/// backward error `|b - A * x| / (|A| * |x| + |b|)` (infinity norms) is at
+	/// most `sqrt(n) * f64::EPSILON`. Fails with `NoConvergence` when `A` is
+	/// too ill-conditioned for single precision, roughly `cond(A) > 1e7`.
+	pub fn solve_mixed(&self, b: &Vector<f64>) -> Result<MixedSolution, LinalgError> {
+		assert!(self.is_square());
+		let n = self.nrows;
+		assert!(b.size() == n);
+		let single = Matrix {
+			nrows: n,
+			ncols: n,
+			order: self.order,
+			v: self.v.iter().map(|&x| x as f32).collect(),
+		};
+		let lu = single.lu();
+		// Singular in single precision says nothing about `A` itself
+		if lu.is_singular() {
+			return Err(LinalgError::NoConvergence);
+		}
+		let solve = |r: &[f64]| -> Result<Vec<f64>, LinalgError> {
+			let r = Vector { v: r.iter().map(|&x| x as f32).collect() };
+			Ok(lu.solve(&r)?.v.iter().map(|&x| x as f64).collect())
+		};
+
+		let anorm = self.norm_inf();
+		let bnorm = b.norm_inf();
+		let tol = (n as f64).sqrt() * f64::EPSILON;
+		let mut x = solve(&b.v)?;
+		let mut error_estimate = 1.0;
+		for k in 0..(MAX_REFINEMENTS + 1) {
+			let mut r = b.v.clone();
+			for (i, ri) in r.iter_mut().enumerate() {
+				for (j, xj) in x.iter().enumerate() {
+					*ri -= self[(i, j)] * xj;
+				}
+			}
+			let rnorm = r.iter().fold(0.0f64, |m, x| m.max(x.abs()));
+			let xnorm = x.iter().fold(0.0f64, |m, x| m.max(x.abs()));
+			let denom = anorm * xnorm + bnorm;
+			let backward_error = if denom == 0.0 { 0.0 } else { rnorm / denom };
+			if backward_error <= tol {
+				return Ok(MixedSolution {
+					x: Vector { v: x },
+					iterations: k,
+					backward_error,
+					error_estimate,
+				});
+			}
+			if k == MAX_REFINEMENTS {
+				break;
+			}
+
+			let d = solve(&r)?;
+			let dnorm = d.iter().fold(0.0f64, |m, x| m.max(x.abs()));
+			for (xi, di) in x.iter_mut().zip(d.iter()) {
+				*xi += di;
+			}
+			let xnorm = x.iter().fold(0.0f64, |m, x| m.max(x.abs()));
+			error_estimate = if xnorm == 0.0 { 0.0 } else { dnorm / xnorm };
+		}
+		Err(LinalgError::NoConvergence)
+	}
+}
+
+impl MixedSolution {
+	pub fn x(&self) -> &Vector<f64> {
+		&self.x
+	}
+
+	pub fn into_x(self) -> Vector<f64> {
+		self.x
+	}
+
+	/// Number of refinement steps after the initial single precision solve.
+	pub fn iterations(&self) -> usize {
+		self.iterations
+	}
+
+	/// Normwise backward error of the returned solution.
+	pub fn backward_error(&self) -> f64 {
+		self.backward_error
+	}
+
+	/// Relative size `|dx| / |x|` of the last correction, an estimate of the
+	/// forward error before that step.
+	pub fn error_estimate(&self) -> f64 {
+		self.error_estimate
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, Vector, LinalgError};
+
+	fn hilbert(n: usize) -> Matrix<f64> {
+		let mut h = Matrix::new_zero(n, n);
+		for i in 0..n {
+			for j in 0..n {
+				h[(i, j)] = 1.0 / (i + j + 1) as f64;
+			}
+		}
+		h
+	}
+
+	#[test]
+	fn test_solve_mixed() {
+		// cond(H_4) is about 1.6e4
+		let a = hilbert(4);
+		let b = Vector::new(&[1.0, 2.0, 3.0, 4.0]);
+		let sol = a.solve_mixed(&b).unwrap();
+		assert!(sol.iterations() > 0);
+		assert!(sol.backward_error() <= 2.0 * f64::EPSILON);
+		let exact = a.lu().solve(&b).unwrap();
+		for i in 0..4 {
+			assert!((sol.x()[i] - exact[i]).abs() <= 1e-10 * exact[i].abs());
+		}
+	}
+
+	#[test]
+	fn test_solve_mixed_ill_conditioned() {
+		// cond(H_10) is about 1.6e13, far beyond single precision
+		let a = hilbert(10);
+		let b = Vector::new(&[1.0; 10]);
+		assert!(a.solve_mixed(&b).err() == Some(LinalgError::NoConvergence));
+	}
+}
@@ -0,0 +1,152 @@
+use traits::Float;
+use linalg::{Matrix, Vector, LinalgError};
+
+// Euclidean norm of the values, scaled so that squaring can neither overflow
+// nor underflow (LAPACK xLASSQ)
+fn scaled_norm2<'a, T: Float + 'a, I: Iterator<Item = &'a T>>(values: I) -> T {
+	let mut scale = T::zero();
+	let mut ssq = T::one();
+	for &x in values {
+		if x == T::zero() {
+			continue;
+		}
+		let a = x.abs();
+		if scale < a {
+			let r = scale / a;
+			ssq = T::one() + ssq * r * r;
+			scale = a;
+		} else {
+			let r = a / scale;
+			ssq = ssq + r * r;
+		}
+	}
+	scale * ssq.sqrt()
+}
+
+fn max_abs<'a, T: Float + 'a, I: Iterator<Item = &'a T>>(values: I) -> T {
+	values.fold(T::zero(), |m, &x| if x.abs() > m { x.abs() } else { m })
+}
+
+impl<T: Float> Vector<T> {
+	pub fn norm1(&self) -> T {
+		self.v.iter().fold(T::zero(), |s, &x| s + x.abs())
+	}
+
+	pub fn norm2(&self) -> T {
+		scaled_norm2(self.v.iter())
+	}
+
+	pub fn norm_inf(&self) -> T {
+		max_abs(self.v.iter())
+	}
+
+	/// `(sum |x_i|^p)^(1/p)` for `p >= 1`, an infinite `p` gives `norm_inf`.
+	pub fn norm_p(&self, p: T) -> T {
+		assert!(p >= T::one());
+		if p == T::one() {
+			return self.norm1();
+		}
+		if p == T::one() + T::one() {
+			return self.norm2();
+		}
+		let scale = self.norm_inf();
+		if p.to_f64().is_infinite() || scale == T::zero() {
+			return scale;
+		}
+		let s = self.v.iter().fold(T::zero(), |s, &x| s + (x.abs() / scale).powf(p));
+		scale * s.powf(T::one() / p)
+	}
+}
+
+impl<T: Float> Matrix<T> {
+	/// Maximum absolute column sum.
+	pub fn norm1(&self) -> T {
+		let mut max = T::zero();
+		for j in 0..self.ncols {
+			let mut s = T::zero();
+			for i in 0..self.nrows {
+				s = s + self[(i, j)].abs();
+			}
+			if s > max {
+				max = s;
+			}
+		}
+		max
+	}
+
+	/// Maximum absolute row sum.
+	pub fn norm_inf(&self) -> T {
+		let mut max = T::zero();
+		for i in 0..self.nrows {
+			let mut s = T::zero();
+			for j in 0..self.ncols {
+				s = s + self[(i, j)].abs();
+			}
+			if s > max {
+				max = s;
+			}
+		}
+		max
+	}
+
+	pub fn norm_frobenius(&self) -> T {
+		scaled_norm2(self.v.iter())
+	}
+
+	/// Largest absolute entry. Not submultiplicative.
+	pub fn norm_max(&self) -> T {
+		max_abs(self.v.iter())
+	}
+
+	/// Spectral norm, the largest singular value.
+	pub fn norm2(&self) -> Result<T, LinalgError> {
+		let s = self.singular_values()?;
+		Ok(s.first().map_or(T::zero(), |&x| x))
+	}
+
+	/// Nuclear norm, the sum of the singular values.
+	pub fn norm_nuclear(&self) -> Result<T, LinalgError> {
+		let s = self.singular_values()?;
+		Ok(s.iter().fold(T::zero(), |acc, &x| acc + x))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, Vector};
+
+	#[test]
+	fn test_vector_norms() {
+		let v = Vector::new(&[3.0f64, -4.0, 0.0]);
+		assert_eq!(v.norm1(), 7.0);
+		assert_eq!(v.norm2(), 5.0);
+		assert_eq!(v.norm_inf(), 4.0);
+		assert_eq!(v.norm_p(1.0), 7.0);
+		assert!((v.norm_p(3.0) - 91.0f64.powf(1.0 / 3.0)).abs() < 1e-14);
+		assert_eq!(v.norm_p(f64::INFINITY), 4.0);
+	}
+
+	#[test]
+	fn test_no_overflow() {
+		let v = Vector::new(&[3e200f64, 4e200]);
+		assert!((v.norm2() / 5e200 - 1.0).abs() < 1e-15);
+		assert!((v.norm_p(3.0) / (91.0f64.powf(1.0 / 3.0) * 1e200) - 1.0).abs() < 1e-14);
+		let w = Vector::new(&[3e-200f64, 4e-200]);
+		assert!((w.norm2() / 5e-200 - 1.0).abs() < 1e-15);
+		let a: Matrix<f32> = Matrix::new(1,2, &[3e30, 4e30]);
+		assert!((a.norm_frobenius() / 5e30 - 1.0).abs() < 1e-6);
+	}
+
+	#[test]
+	fn test_matrix_norms() {
+		let a: Matrix<f64> = Matrix::new(2,3, &[1.0,-2.0,3.0,-4.0,5.0,-6.0]);
+		assert_eq!(a.norm1(), 9.0);
+		assert_eq!(a.norm_inf(), 15.0);
+		assert_eq!(a.norm_max(), 6.0);
+		assert!((a.norm_frobenius() - 91.0f64.sqrt()).abs() < 1e-14);
+
+		let d: Matrix<f64> = Matrix::new(2,2, &[3.0,0.0,0.0,-4.0]);
+		assert!((d.norm2().unwrap() - 4.0).abs() < 1e-14);
+		assert!((d.norm_nuclear().unwrap() - 7.0).abs() < 1e-14);
+	}
+}
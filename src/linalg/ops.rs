@@ -0,0 +1,312 @@
+use traits::{Zero, One};
+use linalg::{Matrix, Vector};
+use std::ops::{Add, Sub, Mul, Div, Neg, AddAssign, SubAssign, MulAssign, DivAssign};
+
+// Elementwise `+` and `-` for every owned/borrowed combination. An owned left
+// operand is reused for the result. In-place sums are `+=` and `-=`: inherent
+// `add` and `sub` methods would be shadowed by these impls wherever `Add` or
+// `Sub` is imported, so `Matrix` and `Vector` no longer have them.
+macro_rules! elementwise_impl {
+    ($t:ident, $imp:ident, $method:ident, $assign:ident, $assign_method:ident, $op:tt) => (
+    	impl<T> $assign<&$t<T>> for $t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+
+    		fn $assign_method(&mut self, other: &$t<T>) {
+    			self.zip_apply(other, |x, y| x $op y);
+    		}
+    	}
+
+    	impl<T> $assign<$t<T>> for $t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+
+    		fn $assign_method(&mut self, other: $t<T>) {
+    			$assign::$assign_method(self, &other);
+    		}
+    	}
+
+    	impl<T> $imp<&$t<T>> for $t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = $t<T>;
+
+    		fn $method(mut self, other: &$t<T>) -> $t<T> {
+    			$assign::$assign_method(&mut self, other);
+    			self
+    		}
+    	}
+
+    	impl<T> $imp<$t<T>> for $t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = $t<T>;
+
+    		fn $method(self, other: $t<T>) -> $t<T> {
+    			$imp::$method(self, &other)
+    		}
+    	}
+
+    	impl<T> $imp<&$t<T>> for &$t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = $t<T>;
+
+    		fn $method(self, other: &$t<T>) -> $t<T> {
+    			$imp::$method(self.clone(), other)
+    		}
+    	}
+
+    	impl<T> $imp<$t<T>> for &$t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = $t<T>;
+
+    		fn $method(self, other: $t<T>) -> $t<T> {
+    			$imp::$method(self.clone(), &other)
+    		}
+    	}
+    )
+}
+
+// Multiplication and division by a scalar, and negation.
+macro_rules! scalar_impl {
+    ($t:ident) => (
+    	impl<T> MulAssign<T> for $t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+
+    		fn mul_assign(&mut self, other: T) {
+    			self.scalar(other);
+    		}
+    	}
+
+    	impl<T> DivAssign<T> for $t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+
+    		fn div_assign(&mut self, other: T) {
+    			for x in self.v.iter_mut() {
+    				*x = *x / other;
+    			}
+    		}
+    	}
+
+    	impl<T> Mul<T> for $t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = $t<T>;
+
+    		fn mul(mut self, other: T) -> $t<T> {
+    			self *= other;
+    			self
+    		}
+    	}
+
+    	impl<T> Mul<T> for &$t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = $t<T>;
+
+    		fn mul(self, other: T) -> $t<T> {
+    			self.clone() * other
+    		}
+    	}
+
+    	impl<T> Div<T> for $t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = $t<T>;
+
+    		fn div(mut self, other: T) -> $t<T> {
+    			self /= other;
+    			self
+    		}
+    	}
+
+    	impl<T> Div<T> for &$t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = $t<T>;
+
+    		fn div(self, other: T) -> $t<T> {
+    			self.clone() / other
+    		}
+    	}
+
+    	impl<T> Neg for $t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq + Neg<Output = T> {
+    		type Output = $t<T>;
+
+    		fn neg(mut self) -> $t<T> {
+    			for x in self.v.iter_mut() {
+    				*x = -*x;
+    			}
+    			self
+    		}
+    	}
+
+    	impl<T> Neg for &$t<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq + Neg<Output = T> {
+    		type Output = $t<T>;
+
+    		fn neg(self) -> $t<T> {
+    			-self.clone()
+    		}
+    	}
+    )
+}
+
+// `A * x` and `x^T * A` with either operand owned or borrowed.
+macro_rules! matrix_vector_impl {
+    ($($a:ty, $x:ty;)*) => ($(
+    	impl<T> Mul<$x> for $a where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = Vector<T>;
+
+    		fn mul(self, other: $x) -> Vector<T> {
+    			self.mul_vector(&other)
+    		}
+    	}
+
+    	impl<T> Mul<$a> for $x where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = Vector<T>;
+
+    		fn mul(self, other: $a) -> Vector<T> {
+    			other.vector_mul(&self)
+    		}
+    	}
+    )*)
+}
+
+// Matrix products with either operand owned or borrowed.
+macro_rules! matmul_impl {
+    ($($a:ty, $b:ty;)*) => ($(
+    	impl<T> Mul<$b> for $a where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+    		type Output = Matrix<T>;
+
+    		fn mul(self, other: $b) -> Matrix<T> {
+    			self.matmul(&other)
+    		}
+    	}
+    )*)
+}
+
+macro_rules! matmul_assign_impl {
+    ($($b:ty;)*) => ($(
+    	impl<T> MulAssign<$b> for Matrix<T> where T:
+    		Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> +
+    		Div<Output = T> + PartialEq {
+
+    		fn mul_assign(&mut self, other: $b) {
+    			*self = self.matmul(&other);
+    		}
+    	}
+    )*)
+}
+
+// `scalar * x` for the primitive scalars. Coherence rules out a generic
+// `impl<T> Mul<Matrix<T>> for T`.
+macro_rules! scalar_lhs_impl {
+    ($t:ident, $($p:ty)*) => ($(
+    	impl Mul<$t<$p>> for $p {
+    		type Output = $t<$p>;
+
+    		fn mul(self, other: $t<$p>) -> $t<$p> {
+    			other * self
+    		}
+    	}
+
+    	impl Mul<&$t<$p>> for $p {
+    		type Output = $t<$p>;
+
+    		fn mul(self, other: &$t<$p>) -> $t<$p> {
+    			other * self
+    		}
+    	}
+    )*)
+}
+
+elementwise_impl!(Matrix, Add, add, AddAssign, add_assign, +);
+elementwise_impl!(Matrix, Sub, sub, SubAssign, sub_assign, -);
+elementwise_impl!(Vector, Add, add, AddAssign, add_assign, +);
+elementwise_impl!(Vector, Sub, sub, SubAssign, sub_assign, -);
+scalar_impl!(Matrix);
+scalar_impl!(Vector);
+matmul_impl! {
+	Matrix<T>, Matrix<T>;
+	Matrix<T>, &Matrix<T>;
+	&Matrix<T>, Matrix<T>;
+	&Matrix<T>, &Matrix<T>;
+}
+matmul_assign_impl! {
+	Matrix<T>;
+	&Matrix<T>;
+}
+matrix_vector_impl! {
+	Matrix<T>, Vector<T>;
+	Matrix<T>, &Vector<T>;
+	&Matrix<T>, Vector<T>;
+	&Matrix<T>, &Vector<T>;
+}
+scalar_lhs_impl!(Matrix, u8 u16 u32 usize i8 i16 i32 isize f32 f64);
+scalar_lhs_impl!(Vector, u8 u16 u32 usize i8 i16 i32 isize f32 f64);
+
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, Vector, StorageOrder};
+
+	#[test]
+	fn test_matrix_ops() {
+		let a = Matrix::new(2, 2, &[1, 2, 3, 4]);
+		let b = Matrix::new(2, 2, &[5, 6, 7, 8]);
+		assert!((&a + &b).equals(&Matrix::new(2, 2, &[6, 8, 10, 12])));
+		assert!((&b - &a).equals(&Matrix::new(2, 2, &[4, 4, 4, 4])));
+		assert!((a.clone() + b.clone()).equals(&(&a + &b)));
+		assert!((&a - b.clone()).equals(&(a.clone() - &b)));
+		assert!((&a * &b).equals(&Matrix::new(2, 2, &[19, 22, 43, 50])));
+		assert!((a.clone() * b.clone()).equals(&(&a * &b)));
+		assert!((-&a).equals(&Matrix::new(2, 2, &[-1, -2, -3, -4])));
+		assert!((&a * 3).equals(&(3 * &a)));
+		assert!((a.clone() * 2 / 2).equals(&a));
+
+		let mut c = a.clone();
+		c += &b;
+		c -= a.clone();
+		assert!(c.equals(&b));
+		c *= &a;
+		assert!(c.equals(&(&b * &a)));
+		c *= 2;
+		c /= 2;
+		assert!(c.equals(&(&b * &a)));
+
+		let cm = b.to_order(StorageOrder::ColMajor);
+		assert!((&a + &cm).equals(&(&a + &b)));
+		assert!((2.0 * Matrix::new(1, 2, &[0.5f64, 1.5])).equals(&Matrix::new(1, 2, &[1.0, 3.0])));
+	}
+
+	#[test]
+	fn test_vector_ops() {
+		let a = Vector::new(&[1.0, 2.0, 3.0]);
+		let b = Vector::new(&[4.0, 5.0, 6.0]);
+		assert!((&a + &b).equals(&Vector::new(&[5.0, 7.0, 9.0])));
+		assert!((b.clone() - a.clone()).equals(&Vector::new(&[3.0, 3.0, 3.0])));
+		assert!((-&a).equals(&(a.clone() * -1.0)));
+		assert!((&b / 2.0).equals(&(0.5 * &b)));
+
+		let mut c = a.clone();
+		c += b;
+		c -= &a;
+		c *= 2.0;
+		c /= 4.0;
+		assert!(c.equals(&Vector::new(&[2.0, 2.5, 3.0])));
+	}
+}
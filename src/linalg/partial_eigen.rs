@@ -0,0 +1,573 @@
+//! Eigensolvers for a few eigenpairs of a large matrix.
+//!
+//! Like the solvers in `iterative` they only need matrix-vector products, so
+//! `A` can be a dense `Matrix` or a closure. The operator does not know its
+//! dimension, hence the explicit `n`. For `lanczos` and `arnoldi`,
+//! `SolverConfig::restart` is the Krylov subspace dimension kept between
+//! restarts and `max_iterations` bounds the number of products with `A`.
+
+use traits::Float;
+use linalg::{Matrix, Vector};
+use linalg::iterative::{LinearOperator, SolverConfig, StopReason, dot, norm, axpy};
+
+/// Which end of the spectrum to compute.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Which {
+	/// Largest algebraic value for `lanczos`, largest magnitude for `arnoldi`.
+	Largest,
+	/// Smallest algebraic value for `lanczos`, smallest magnitude for
+	/// `arnoldi`. Interior and small magnitude eigenvalues converge slowly,
+	/// `inverse_iteration` is usually the better choice for those.
+	Smallest,
+}
+
+/// Approximate eigenpairs. Complex pairs follow the layout of `Eigen`:
+/// adjacent, positive imaginary part first, with the real and imaginary
+/// parts of the eigenvector in the two corresponding columns of `vectors`.
+pub struct PartialEigen<T> {
+	real: Vec<T>,
+	imag: Vec<T>,
+	vectors: Matrix<T>,
+	residuals: Vec<T>,
+	iterations: usize,
+	reason: StopReason,
+}
+
+impl<T> PartialEigen<T> {
+	pub fn real(&self) -> &[T] {
+		&self.real
+	}
+
+	pub fn imag(&self) -> &[T] {
+		&self.imag
+	}
+
+	/// Unit eigenvectors, one column per eigenvalue.
+	pub fn vectors(&self) -> &Matrix<T> {
+		&self.vectors
+	}
+
+	/// Residual norms `|A * x - lambda * x|` of each pair.
+	pub fn residuals(&self) -> &[T] {
+		&self.residuals
+	}
+
+	pub fn iterations(&self) -> usize {
+		self.iterations
+	}
+
+	pub fn reason(&self) -> StopReason {
+		self.reason
+	}
+
+	pub fn converged(&self) -> bool {
+		self.reason == StopReason::Converged
+	}
+}
+
+/// Power iteration for the eigenvalue of largest magnitude, which has to be
+/// real and strictly dominant. Stops once `|A * x - lambda * x|` drops below
+/// `tolerance * |A * x|`.
+pub fn power_iteration<T, A>(a: &A, n: usize, x0: Option<&Vector<T>>, config: &SolverConfig<T>)
+	-> PartialEigen<T> where T: Float, A: LinearOperator<T> {
+	let mut x = start(n, x0);
+	let mut y = vec![T::zero(); n];
+	let mut lambda = T::zero();
+	let mut residual = T::zero();
+	for k in 1..(config.max_iterations + 1) {
+		a.apply(&x, &mut y);
+		lambda = dot(&x, &y);
+		let ynorm = norm(&y);
+		let mut r = y.clone();
+		axpy(-lambda, &x, &mut r);
+		residual = norm(&r);
+		if residual <= config.tolerance * ynorm {
+			return single(x, lambda, residual, k, StopReason::Converged);
+		}
+		x = y.iter().map(|&yi| yi / ynorm).collect();
+	}
+	single(x, lambda, residual, config.max_iterations, StopReason::MaxIterations)
+}
+
+/// Inverse iteration with a fixed shift, converging to the eigenvalue
+/// closest to `shift`. `A - shift * I` is factored once; if the shift is an
+/// exact eigenvalue it is perturbed slightly.
+pub fn inverse_iteration<T: Float>(a: &Matrix<T>, shift: T, x0: Option<&Vector<T>>,
+	config: &SolverConfig<T>) -> PartialEigen<T> {
+	assert!(a.is_square());
+	let n = a.nrows();
+	let shifted = |shift: T| {
+		let mut b = a.clone();
+		for i in 0..n {
+			b[(i, i)] = b[(i, i)] - shift;
+		}
+		b.lu()
+	};
+	let mut lu = shifted(shift);
+	if lu.is_singular() {
+		let scale = a.norm1() + shift.abs();
+		let delta = T::epsilon() * if scale > T::one() { scale } else { T::one() };
+		lu = shifted(shift + delta);
+	}
+
+	let mut x = start(n, x0);
+	let mut ax = vec![T::zero(); n];
+	let mut lambda = shift;
+	let mut residual = T::zero();
+	for k in 1..(config.max_iterations + 1) {
+		let y = match lu.solve(&Vector { v: x.clone() }) {
+			Ok(y) => y.v,
+			Err(_) => return single(x, lambda, residual, k, StopReason::Breakdown),
+		};
+		let ynorm = norm(&y);
+		x = y.iter().map(|&yi| yi / ynorm).collect();
+		a.apply(&x, &mut ax);
+		lambda = dot(&x, &ax);
+		let mut r = ax.clone();
+		axpy(-lambda, &x, &mut r);
+		residual = norm(&r);
+		if residual <= config.tolerance * norm(&ax) {
+			return single(x, lambda, residual, k, StopReason::Converged);
+		}
+	}
+	single(x, lambda, residual, config.max_iterations, StopReason::MaxIterations)
+}
+
+/// Implicitly restarted Lanczos for `k` extreme eigenpairs of a symmetric
+/// `A`, sorted from the requested end of the spectrum inwards.
+pub fn lanczos<T, A>(a: &A, n: usize, k: usize, which: Which, x0: Option<&Vector<T>>,
+	config: &SolverConfig<T>) -> PartialEigen<T> where T: Float, A: LinearOperator<T> {
+	restarted_arnoldi(a, n, k, which, true, x0, config)
+}
+
+/// Implicitly restarted Arnoldi for `k` eigenpairs of largest or smallest
+/// magnitude of a general `A`. If the `k`-th eigenvalue is one of a complex
+/// pair, both are returned, so there may be `k + 1` pairs.
+pub fn arnoldi<T, A>(a: &A, n: usize, k: usize, which: Which, x0: Option<&Vector<T>>,
+	config: &SolverConfig<T>) -> PartialEigen<T> where T: Float, A: LinearOperator<T> {
+	restarted_arnoldi(a, n, k, which, false, x0, config)
+}
+
+fn single<T: Float>(x: Vec<T>, lambda: T, residual: T, iterations: usize, reason: StopReason)
+	-> PartialEigen<T> {
+	let n = x.len();
+	PartialEigen {
+		real: vec![lambda],
+		imag: vec![T::zero()],
+		vectors: Matrix::new(n, 1, &x),
+		residuals: vec![residual],
+		iterations,
+		reason,
+	}
+}
+
+// Normalized starting vector, `x0` or a fixed pseudo random one.
+fn start<T: Float>(n: usize, x0: Option<&Vector<T>>) -> Vec<T> {
+	let mut x = match x0 {
+		Some(x0) => {
+			assert!(x0.size() == n);
+			x0.v.clone()
+		}
+		None => pseudo_random(n, 1),
+	};
+	let xnorm = norm(&x);
+	if xnorm == T::zero() {
+		x = pseudo_random(n, 1);
+	}
+	let xnorm = norm(&x);
+	x.iter().map(|&xi| xi / xnorm).collect()
+}
+
+// Deterministic values in [-1, 1), so results are reproducible.
+fn pseudo_random<T: Float>(n: usize, seed: u64) -> Vec<T> {
+	let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+	(0..n).map(|_| {
+		state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+		T::from_f64((state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0)
+	}).collect()
+}
+
+// A unit vector orthogonal to all of `v`, used when the Krylov subspace
+// becomes invariant.
+fn orthogonal_vector<T: Float>(n: usize, v: &[Vec<T>]) -> Vec<T> {
+	let mut seed = 2;
+	loop {
+		let mut f = pseudo_random(n, seed);
+		let before = norm(&f);
+		for _ in 0..2 {
+			for vi in v {
+				let c = dot(vi, &f);
+				axpy(-c, vi, &mut f);
+			}
+		}
+		let after = norm(&f);
+		if after > before * T::from_f64(1e-3) {
+			return f.iter().map(|&fi| fi / after).collect();
+		}
+		seed += 1;
+	}
+}
+
+// Extends the Arnoldi factorization `A * V = V * H + f * e^T` from `from` to
+// `m` columns. Orthogonalization is repeated once to keep `V` orthonormal.
+// In the symmetric case entries of `H` above the superdiagonal are rounding
+// noise and are dropped, leaving the Lanczos tridiagonal.
+fn extend<T, A>(a: &A, v: &mut Vec<Vec<T>>, h: &mut Matrix<T>, f: &mut Vec<T>, from: usize,
+	symmetric: bool) -> usize where T: Float, A: LinearOperator<T> {
+	let (n, m) = (f.len(), h.nrows());
+	for j in from..m {
+		let beta = norm(f);
+		let mut scale = beta;
+		if j > 0 {
+			for i in 0..j {
+				scale = scale.hypot(h[(i, j - 1)]);
+			}
+		}
+		if beta <= T::epsilon() * scale || beta == T::zero() {
+			*f = orthogonal_vector(n, v);
+			if j > 0 {
+				h[(j, j - 1)] = T::zero();
+			}
+		} else {
+			if j > 0 {
+				h[(j, j - 1)] = beta;
+			}
+			for fi in f.iter_mut() {
+				*fi = *fi / beta;
+			}
+		}
+		v.push(f.clone());
+
+		let mut w = vec![T::zero(); n];
+		a.apply(&v[j], &mut w);
+		for _ in 0..2 {
+			for (i, vi) in v.iter().enumerate() {
+				let c = dot(vi, &w);
+				h[(i, j)] = h[(i, j)] + c;
+				axpy(-c, vi, &mut w);
+			}
+		}
+		if symmetric {
+			for i in 0..j.saturating_sub(1) {
+				h[(i, j)] = T::zero();
+			}
+		}
+		*f = w;
+	}
+	m - from
+}
+
+// Ritz values and the eigenvectors of `H`, the latter in the layout of
+// `Eigen`.
+struct Ritz<T> {
+	real: Vec<T>,
+	imag: Vec<T>,
+	y: Matrix<T>,
+}
+
+fn ritz<T: Float>(h: &Matrix<T>, symmetric: bool) -> Option<Ritz<T>> {
+	if symmetric {
+		let eig = h.symmetric_eigen().ok()?;
+		Some(Ritz {
+			real: eig.values().to_vec(),
+			imag: vec![T::zero(); h.nrows()],
+			y: eig.vectors().clone(),
+		})
+	} else {
+		let eig = h.eigen().ok()?;
+		Some(Ritz {
+			real: eig.real().to_vec(),
+			imag: eig.imag().to_vec(),
+			y: eig.vectors().clone(),
+		})
+	}
+}
+
+// Columns of `y` holding the real and imaginary part of Ritz vector `i`.
+fn parts<T: Float>(imag: &[T], i: usize) -> (usize, Option<usize>) {
+	if imag[i] > T::zero() {
+		(i, Some(i + 1))
+	} else if imag[i] < T::zero() {
+		(i - 1, Some(i))
+	} else {
+		(i, None)
+	}
+}
+
+fn restarted_arnoldi<T, A>(a: &A, n: usize, k: usize, which: Which, symmetric: bool,
+	x0: Option<&Vector<T>>, config: &SolverConfig<T>) -> PartialEigen<T>
+	where T: Float, A: LinearOperator<T> {
+	assert!(k >= 1 && k <= n);
+	let m = ::std::cmp::min(n, ::std::cmp::max(config.restart, 2 * k + 1));
+	let mut f = start(n, x0);
+	let mut v: Vec<Vec<T>> = Vec::with_capacity(m);
+	let mut h = Matrix::new_zero(m, m);
+	let mut iterations = 0;
+	let mut kept = 0;
+	loop {
+		iterations += extend(a, &mut v, &mut h, &mut f, kept, symmetric);
+		let ritz = match ritz(&h, symmetric) {
+			Some(ritz) => ritz,
+			None => {
+				let empty = Ritz { real: vec![], imag: vec![], y: Matrix::new_zero(m, 0) };
+				return finish(a, &v, &[], &empty, iterations, StopReason::Breakdown);
+			}
+		};
+		let (real, imag, y) = (&ritz.real, &ritz.imag, &ritz.y);
+
+		let magnitude = |i: usize| if symmetric { real[i] } else { real[i].hypot(imag[i]) };
+		let mut order: Vec<usize> = (0..m).collect();
+		order.sort_by(|&i, &j| {
+			let ord = magnitude(i).partial_cmp(&magnitude(j)).unwrap_or(::std::cmp::Ordering::Equal);
+			if which == Which::Largest { ord.reverse() } else { ord }
+		});
+		// Never split a complex conjugate pair
+		let mut wanted = k;
+		if wanted < m && imag[order[wanted - 1]] > T::zero() {
+			wanted += 1;
+		}
+
+		// Ritz estimates |f| * |e_m^T y|
+		let beta = norm(&f);
+		let converged = order[..wanted].iter().all(|&i| {
+			let (re, im) = parts(imag, i);
+			let mut last = y[(m - 1, re)].abs();
+			if let Some(im) = im {
+				last = last.hypot(y[(m - 1, im)]);
+			}
+			let scale = real[i].hypot(imag[i]);
+			let scale = if scale > T::epsilon() { scale } else { T::epsilon() };
+			beta * last <= config.tolerance * scale
+		});
+		if converged || wanted >= m || iterations >= config.max_iterations {
+			let reason = if converged || wanted >= m {
+				StopReason::Converged
+			} else {
+				StopReason::MaxIterations
+			};
+			let order = &order[..wanted];
+			return finish(a, &v, order, &ritz, iterations, reason);
+		}
+
+		// Implicit restart with the unwanted Ritz values as exact shifts
+		let mut q: Matrix<T> = Matrix::new_identity(m);
+		let mut i = wanted;
+		while i < m {
+			let s = order[i];
+			let mut shifted = h.clone();
+			if imag[s] == T::zero() {
+				for j in 0..m {
+					shifted[(j, j)] = shifted[(j, j)] - real[s];
+				}
+				i += 1;
+			} else {
+				// Double shift (H - mu) * (H - conj(mu)) in real arithmetic
+				shifted = h.matmul(&h);
+				let two = T::one() + T::one();
+				let norm2 = real[s] * real[s] + imag[s] * imag[s];
+				for r in 0..m {
+					for c in 0..m {
+						shifted[(r, c)] = shifted[(r, c)] - two * real[s] * h[(r, c)];
+					}
+					shifted[(r, r)] = shifted[(r, r)] + norm2;
+				}
+				i += 2;
+			}
+			let qi = shifted.qr().q();
+			let mut qt = qi.clone();
+			qt.transpose();
+			h = qt.matmul(&h).matmul(&qi);
+			q = q.matmul(&qi);
+			for r in 2..m {
+				for c in 0..(r - 1) {
+					h[(r, c)] = T::zero();
+				}
+			}
+			if symmetric {
+				for r in 1..m {
+					h[(r - 1, r)] = h[(r, r - 1)];
+					for c in (r + 1)..m {
+						h[(r - 1, c)] = T::zero();
+					}
+				}
+			}
+		}
+
+		// f = V * q_{wanted} * H[wanted, wanted - 1] + f * Q[m - 1, wanted - 1]
+		let hk = h[(wanted, wanted - 1)];
+		let sigma = q[(m - 1, wanted - 1)];
+		let mut f_new: Vec<T> = f.iter().map(|&fi| fi * sigma).collect();
+		let mut v_new = Vec::with_capacity(m);
+		for j in 0..(wanted + 1) {
+			let mut col = vec![T::zero(); n];
+			for (r, vr) in v.iter().enumerate() {
+				axpy(q[(r, j)], vr, &mut col);
+			}
+			if j < wanted {
+				v_new.push(col);
+			} else {
+				axpy(hk, &col, &mut f_new);
+			}
+		}
+		v = v_new;
+		f = f_new;
+		for r in 0..m {
+			for c in 0..m {
+				if r >= wanted || c >= wanted {
+					h[(r, c)] = T::zero();
+				}
+			}
+		}
+		kept = wanted;
+	}
+}
+
+// Ritz vectors `V * y` for the selected Ritz values, with true residuals.
+fn finish<T, A>(a: &A, v: &[Vec<T>], order: &[usize], ritz: &Ritz<T>, iterations: usize,
+	reason: StopReason) -> PartialEigen<T> where T: Float, A: LinearOperator<T> {
+	let (real, imag, y) = (&ritz.real, &ritz.imag, &ritz.y);
+	let n = v.first().map_or(0, |v0| v0.len());
+	let ritz_vector = |c: usize| {
+		let mut x = vec![T::zero(); n];
+		for (r, vr) in v.iter().enumerate() {
+			axpy(y[(r, c)], vr, &mut x);
+		}
+		x
+	};
+	let mut vectors = Matrix::new_zero(n, order.len());
+	let mut residuals = Vec::with_capacity(order.len());
+	for (j, &i) in order.iter().enumerate() {
+		let x = ritz_vector(i);
+		for (r, &xr) in x.iter().enumerate() {
+			vectors[(r, j)] = xr;
+		}
+
+		// For x = xr + i * xi and lambda = a + i * b the residual is
+		// (A xr - a xr + b xi) + i * (A xi - a xi - b xr)
+		let (re, im) = parts(imag, i);
+		let xr = ritz_vector(re);
+		let mut r = vec![T::zero(); n];
+		a.apply(&xr, &mut r);
+		axpy(-real[i], &xr, &mut r);
+		let mut res = match im {
+			Some(im) => {
+				let b = imag[re];
+				let xi = ritz_vector(im);
+				axpy(b, &xi, &mut r);
+				let mut s = vec![T::zero(); n];
+				a.apply(&xi, &mut s);
+				axpy(-real[i], &xi, &mut s);
+				axpy(-b, &xr, &mut s);
+				norm(&s)
+			}
+			None => T::zero(),
+		};
+		res = res.hypot(norm(&r));
+		residuals.push(res);
+	}
+	PartialEigen {
+		real: order.iter().map(|&i| real[i]).collect(),
+		imag: order.iter().map(|&i| imag[i]).collect(),
+		vectors,
+		residuals,
+		iterations,
+		reason,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, Vector};
+	use linalg::iterative::{SolverConfig, StopReason, laplacian};
+	use super::*;
+
+	// Eigenvalues 2 - 2 cos(j pi / (n + 1)), j = 1..n
+	fn laplacian_eigenvalue(n: usize, j: usize) -> f64 {
+		2.0 - 2.0 * (j as f64 * ::std::f64::consts::PI / (n as f64 + 1.0)).cos()
+	}
+
+	#[test]
+	fn test_power_and_inverse_iteration() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[4.0,1.0,0.0,1.0,3.0,1.0,0.0,1.0,1.0]);
+		let values = a.symmetric_eigenvalues().unwrap();
+		let config = SolverConfig::new(1e-10, 1000);
+		let eig = power_iteration(&a, 3, None, &config);
+		assert!(eig.converged());
+		assert!((eig.real()[0] - values[2]).abs() < 1e-8);
+		assert!(eig.residuals()[0] < 1e-8);
+
+		let n = 10;
+		let eig = inverse_iteration(&laplacian(n), 1.0, None, &config);
+		assert!(eig.converged());
+		// Closest eigenvalue to 1 is j = 4
+		assert!((eig.real()[0] - laplacian_eigenvalue(n, 4)).abs() < 1e-10);
+
+		// Exact eigenvalue as shift
+		let eig = inverse_iteration(&a, values[1], Some(&Vector::new(&[1.0, 1.0, 1.0])), &config);
+		assert!(eig.converged());
+		assert!((eig.real()[0] - values[1]).abs() < 1e-10);
+	}
+
+	#[test]
+	fn test_lanczos() {
+		let n = 100;
+		let a = laplacian(n);
+		let mut config = SolverConfig::new(1e-10, 5000);
+		config.restart = 20;
+		let eig = lanczos(&a, n, 4, Which::Largest, None, &config);
+		assert!(eig.converged());
+		for j in 0..4 {
+			assert!((eig.real()[j] - laplacian_eigenvalue(n, n - j)).abs() < 1e-8);
+			assert!(eig.residuals()[j] < 1e-7);
+		}
+		let v = eig.vectors();
+		let mut vt = v.clone();
+		vt.transpose();
+		::linalg::assert_close(&vt.matmul(v), &Matrix::new_identity(4), 1e-8);
+
+		// Same operator as a closure
+		let op = |x: &[f64], y: &mut [f64]| a.apply(x, y);
+		let eig = lanczos(&op, n, 3, Which::Smallest, None, &config);
+		assert!(eig.converged());
+		for j in 0..3 {
+			assert!((eig.real()[j] - laplacian_eigenvalue(n, j + 1)).abs() < 1e-8);
+		}
+
+		let eig = lanczos(&a, n, 4, Which::Largest, None, &SolverConfig::new(1e-14, 25));
+		assert_eq!(eig.reason(), StopReason::MaxIterations);
+	}
+
+	#[test]
+	fn test_arnoldi_complex() {
+		// Upper triangular apart from a 2x2 block with eigenvalues 40 +- 5i
+		let n = 30;
+		let mut a = Matrix::new_zero(n, n);
+		for i in 0..n {
+			a[(i, i)] = (i + 1) as f64;
+			for j in (i + 1)..n {
+				a[(i, j)] = 0.5 / (j - i) as f64;
+			}
+		}
+		a[(10, 10)] = 40.0;
+		a[(11, 11)] = 40.0;
+		a[(10, 11)] = 5.0;
+		a[(11, 10)] = -5.0;
+
+		let mut config = SolverConfig::new(1e-10, 2000);
+		config.restart = 12;
+		let eig = arnoldi(&a, n, 3, Which::Largest, None, &config);
+		assert!(eig.converged());
+		assert_eq!(eig.real().len(), 3);
+		assert!((eig.real()[0] - 40.0).abs() < 1e-8 && (eig.imag()[0] - 5.0).abs() < 1e-8);
+		assert!((eig.real()[1] - 40.0).abs() < 1e-8 && (eig.imag()[1] + 5.0).abs() < 1e-8);
+		assert!((eig.real()[2] - 30.0).abs() < 1e-8 && eig.imag()[2] == 0.0);
+		for r in eig.residuals() {
+			assert!(*r < 1e-7);
+		}
+
+		// The pair is not split
+		let eig = arnoldi(&a, n, 1, Which::Largest, None, &config);
+		assert!(eig.converged());
+		assert_eq!(eig.real().len(), 2);
+	}
+}
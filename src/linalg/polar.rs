@@ -0,0 +1,107 @@
+use traits::Float;
+use linalg::{Matrix, LinalgError};
+
+/// Polar decomposition `A = U * P`. For an `m x n` matrix `U` is `m x n`
+/// with orthonormal columns (rows if `m < n`) and `P` is the `n x n`
+/// symmetric positive semidefinite factor `(A^T * A)^(1/2)`.
+pub struct Polar<T> {
+	u: Matrix<T>,
+	p: Matrix<T>,
+}
+
+impl<T: Float> Matrix<T> {
+	pub fn polar(&self) -> Result<Polar<T>, LinalgError> {
+		let svd = self.svd()?;
+		// A = W * S * V^T gives U = W * V^T and P = V * S * V^T
+		let mut vt = svd.v().clone();
+		vt.transpose();
+		let mut vs = svd.v().clone();
+		for (j, &s) in svd.singular_values().iter().enumerate() {
+			for i in 0..vs.nrows() {
+				vs[(i, j)] = vs[(i, j)] * s;
+			}
+		}
+		Ok(Polar {
+			u: svd.u().matmul(&vt),
+			p: vs.matmul(&vt),
+		})
+	}
+
+	/// Orthogonal Procrustes problem: the orthogonal `R` minimizing
+	/// `|A * R - B|_F`, e.g. the rotation aligning the points in the rows of
+	/// `A` with those of `B`. With `proper` set `R` is restricted to
+	/// rotations, `det(R) = 1` (the Kabsch algorithm). The closest rotation to
+	/// a square `M` is `Matrix::new_identity(n).procrustes(&M, true)`.
+	pub fn procrustes(&self, b: &Matrix<T>, proper: bool) -> Result<Matrix<T>, LinalgError> {
+		assert!(self.size() == b.size());
+		let mut at = self.clone();
+		at.transpose();
+		let svd = at.matmul(b).svd()?;
+		let mut u = svd.u().clone();
+		let mut vt = svd.v().clone();
+		vt.transpose();
+		if proper && u.matmul(&vt).determinant() < T::zero() {
+			// Flip the direction belonging to the smallest singular value
+			let k = u.ncols() - 1;
+			for i in 0..u.nrows() {
+				u[(i, k)] = -u[(i, k)];
+			}
+		}
+		Ok(u.matmul(&vt))
+	}
+}
+
+impl<T: Float> Polar<T> {
+	pub fn u(&self) -> &Matrix<T> {
+		&self.u
+	}
+
+	pub fn p(&self) -> &Matrix<T> {
+		&self.p
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, assert_close};
+
+	fn transposed(m: &Matrix<f64>) -> Matrix<f64> {
+		let mut t = m.clone();
+		t.transpose();
+		t
+	}
+
+	#[test]
+	fn test_polar() {
+		let a: Matrix<f64> = Matrix::new(3,2, &[1.0,2.0,3.0,4.0,5.0,7.0]);
+		let polar = a.polar().unwrap();
+		assert_close(&polar.u().matmul(polar.p()), &a, 1e-12);
+		assert_close(&transposed(polar.u()).matmul(polar.u()), &Matrix::new_identity(2), 1e-12);
+		assert_close(polar.p(), &transposed(polar.p()), 1e-12);
+		assert!(polar.p().symmetric_eigenvalues().unwrap().iter().all(|&x| x >= 0.0));
+
+		let wide = transposed(&a);
+		let polar = wide.polar().unwrap();
+		assert_close(&polar.u().matmul(polar.p()), &wide, 1e-12);
+		assert_close(&polar.u().matmul(&transposed(polar.u())), &Matrix::new_identity(2), 1e-12);
+	}
+
+	#[test]
+	fn test_procrustes() {
+		// Points rotated by 90 degrees about z
+		let a: Matrix<f64> = Matrix::new(4,3, &[1.0,0.0,0.0,0.0,2.0,0.0,0.0,0.0,3.0,1.0,1.0,1.0]);
+		let rot: Matrix<f64> = Matrix::new(3,3, &[0.0,1.0,0.0,-1.0,0.0,0.0,0.0,0.0,1.0]);
+		let b = a.matmul(&rot);
+		assert_close(&a.procrustes(&b, true).unwrap(), &rot, 1e-12);
+
+		// A reflection is the best orthogonal fit, but not a rotation
+		let mut mirror: Matrix<f64> = Matrix::new_identity(3);
+		mirror[(2, 2)] = -1.0;
+		let b = a.matmul(&mirror);
+		let r = a.procrustes(&b, false).unwrap();
+		assert_close(&r, &mirror, 1e-12);
+		let r = a.procrustes(&b, true).unwrap();
+		assert!((r.determinant() - 1.0).abs() < 1e-12);
+		assert_close(&transposed(&r).matmul(&r), &Matrix::new_identity(3), 1e-12);
+	}
+}
@@ -0,0 +1,454 @@
+use traits::Float;
+use linalg::{Matrix, Vector, LinalgError};
+
+/// Householder QR factorization, `A = Q * R`.
+pub struct Qr<T> {
+	q: Matrix<T>,
+	r: Matrix<T>,
+}
+
+/// Householder QR factorization with column pivoting, `A * P = Q * R`.
+/// The diagonal of `R` is non-increasing in magnitude, so the numerical rank
+/// can be read off it.
+pub struct ColPivQr<T> {
+	q: Matrix<T>,
+	r: Matrix<T>,
+	perm: Vec<usize>,
+}
+
+impl<T: Float> Matrix<T> {
+	pub fn qr(&self) -> Qr<T> {
+		let (m, n) = self.size();
+		let mut q: Matrix<T> = Matrix::new_identity(m);
+		let mut r = self.clone();
+		let mut v = vec![T::zero(); m];
+		for k in 0..::std::cmp::min(m.saturating_sub(1), n) {
+			householder(&mut q, &mut r, k, &mut v);
+		}
+		Qr {
+			q,
+			r,
+		}
+	}
+
+	pub fn col_piv_qr(&self) -> ColPivQr<T> {
+		let (m, n) = self.size();
+		let mut q: Matrix<T> = Matrix::new_identity(m);
+		let mut r = self.clone();
+		let mut perm: Vec<usize> = (0..n).collect();
+		let mut v = vec![T::zero(); m];
+		for k in 0..::std::cmp::min(m, n) {
+			let mut p = k;
+			let mut max = -T::one();
+			for j in k..n {
+				let mut s = T::zero();
+				for i in k..m {
+					s = s + r[(i, j)] * r[(i, j)];
+				}
+				if s > max {
+					max = s;
+					p = j;
+				}
+			}
+			if p != k {
+				for i in 0..m {
+					let t = r[(i, k)];
+					r[(i, k)] = r[(i, p)];
+					r[(i, p)] = t;
+				}
+				perm.swap(k, p);
+			}
+			if k + 1 < m {
+				householder(&mut q, &mut r, k, &mut v);
+			}
+		}
+		ColPivQr {
+			q,
+			r,
+			perm,
+		}
+	}
+
+	/// Numerical rank from a column pivoted QR factorization.
+	pub fn rank(&self) -> usize {
+		self.col_piv_qr().rank()
+	}
+
+	/// Orthonormal basis of the range of `A`, one column per dimension.
+	pub fn column_space(&self) -> Matrix<T> {
+		let qr = self.col_piv_qr();
+		columns(&qr.q, 0, qr.rank())
+	}
+
+	/// Orthonormal basis of the null space of `A^T`.
+	pub fn left_null_space(&self) -> Matrix<T> {
+		let qr = self.col_piv_qr();
+		columns(&qr.q, qr.rank(), self.nrows)
+	}
+
+	/// Orthonormal basis of the range of `A^T`.
+	pub fn row_space(&self) -> Matrix<T> {
+		let mut t = self.clone();
+		t.transpose();
+		t.column_space()
+	}
+
+	/// Orthonormal basis of the null space of `A`.
+	pub fn null_space(&self) -> Matrix<T> {
+		let mut t = self.clone();
+		t.transpose();
+		t.left_null_space()
+	}
+}
+
+// Applies the Householder reflection H that zeroes `r[k+1.., k]`, as
+// `R <- H * R` and `Q <- Q * H`. `v` is scratch space of length m.
+fn householder<T: Float>(q: &mut Matrix<T>, r: &mut Matrix<T>, k: usize, v: &mut [T]) {
+	let (m, n) = r.size();
+	let mut norm = T::zero();
+	for i in k..m {
+		norm = norm + r[(i, k)] * r[(i, k)];
+	}
+	norm = norm.sqrt();
+	if norm == T::zero() {
+		return;
+	}
+	let alpha = if r[(k, k)] > T::zero() { -norm } else { norm };
+	let mut vv = T::zero();
+	for i in k..m {
+		v[i] = r[(i, k)];
+		if i == k {
+			v[i] = v[i] - alpha;
+		}
+		vv = vv + v[i] * v[i];
+	}
+	let beta = (T::one() + T::one()) / vv;
+
+	r[(k, k)] = alpha;
+	for i in (k + 1)..m {
+		r[(i, k)] = T::zero();
+	}
+	for j in (k + 1)..n {
+		let mut s = T::zero();
+		for i in k..m {
+			s = s + v[i] * r[(i, j)];
+		}
+		s = s * beta;
+		for i in k..m {
+			r[(i, j)] = r[(i, j)] - s * v[i];
+		}
+	}
+
+	for i in 0..m {
+		let mut s = T::zero();
+		for l in k..m {
+			s = s + q[(i, l)] * v[l];
+		}
+		s = s * beta;
+		for l in k..m {
+			q[(i, l)] = q[(i, l)] - s * v[l];
+		}
+	}
+}
+
+// Number of diagonal entries of `r` above `max(m, n) * eps * max |r_ii|`.
+fn diagonal_rank<T: Float>(r: &Matrix<T>) -> usize {
+	let (m, n) = r.size();
+	let k = ::std::cmp::min(m, n);
+	let mut max = T::zero();
+	for i in 0..k {
+		if r[(i, i)].abs() > max {
+			max = r[(i, i)].abs();
+		}
+	}
+	let tol = T::from_f64(::std::cmp::max(m, n) as f64) * T::epsilon() * max;
+	(0..k).filter(|&i| r[(i, i)].abs() > tol).count()
+}
+
+// Columns `from..to` of `q`.
+fn columns<T: Float>(q: &Matrix<T>, from: usize, to: usize) -> Matrix<T> {
+	let mut c = Matrix::new_zero(q.nrows(), to - from);
+	for i in 0..q.nrows() {
+		for j in from..to {
+			c[(i, j - from)] = q[(i, j)];
+		}
+	}
+	c
+}
+
+impl<T: Float> Qr<T> {
+	/// The full `m x m` orthogonal factor.
+	pub fn q(&self) -> Matrix<T> {
+		self.q.clone()
+	}
+
+	/// The first `min(m, n)` columns of `Q`.
+	pub fn thin_q(&self) -> Matrix<T> {
+		let (m, n) = self.r.size();
+		columns(&self.q, 0, ::std::cmp::min(m, n))
+	}
+
+	/// The full `m x n` upper triangular factor.
+	pub fn r(&self) -> Matrix<T> {
+		self.r.clone()
+	}
+
+	/// The first `min(m, n)` rows of `R`.
+	pub fn thin_r(&self) -> Matrix<T> {
+		let (m, n) = self.r.size();
+		let k = ::std::cmp::min(m, n);
+		let mut r = Matrix::new_zero(k, n);
+		for i in 0..k {
+			for j in i..n {
+				r[(i, j)] = self.r[(i, j)];
+			}
+		}
+		r
+	}
+
+	/// Number of diagonal entries of `R` that are not negligible.
+	pub fn rank(&self) -> usize {
+		diagonal_rank(&self.r)
+	}
+
+	pub fn is_full_rank(&self) -> bool {
+		let (m, n) = self.r.size();
+		self.rank() == ::std::cmp::min(m, n)
+	}
+
+	/// Least squares solution of `A * x = b` for `m >= n`.
+	pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, LinalgError> {
+		let x = self.solve_matrix(&b.clone().to_col_matrix())?;
+		Ok(Vector { v: x.v })
+	}
+
+	pub fn solve_matrix(&self, b: &Matrix<T>) -> Result<Matrix<T>, LinalgError> {
+		let (m, n) = self.r.size();
+		assert!(m >= n);
+		assert!(b.nrows() == m);
+		if !self.is_full_rank() {
+			return Err(LinalgError::RankDeficient);
+		}
+		let mut x = Matrix::new_zero(n, b.ncols());
+		for c in 0..b.ncols() {
+			// x = Q^T * b, truncated to the first n rows
+			for i in 0..n {
+				let mut s = T::zero();
+				for l in 0..m {
+					s = s + self.q[(l, i)] * b[(l, c)];
+				}
+				x[(i, c)] = s;
+			}
+			for i in (0..n).rev() {
+				let mut s = x[(i, c)];
+				for l in (i + 1)..n {
+					s = s - self.r[(i, l)] * x[(l, c)];
+				}
+				x[(i, c)] = s / self.r[(i, i)];
+			}
+		}
+		Ok(x)
+	}
+
+	/// Turns the factorization of `A` into that of `A + u * v^T` in
+	/// `O(m^2 + m * n)`. Downdates are updates with `-u`.
+	pub fn update(&mut self, u: &Vector<T>, v: &Vector<T>) {
+		let (m, n) = self.r.size();
+		assert!(u.size() == m && v.size() == n);
+		if m == 0 {
+			return;
+		}
+
+		// A + u * v^T = Q * (R + w * v^T) with w = Q^T * u
+		let mut w = vec![T::zero(); m];
+		for (i, wi) in w.iter_mut().enumerate() {
+			for l in 0..m {
+				*wi = *wi + self.q[(l, i)] * u[l];
+			}
+		}
+		// Rotate w into a multiple of e_1, which makes R upper Hessenberg
+		for k in (1..m).rev() {
+			let (c, s) = givens(w[k - 1], w[k]);
+			w[k - 1] = c * w[k - 1] + s * w[k];
+			w[k] = T::zero();
+			self.rotate(k - 1, k, c, s, k - 1);
+		}
+		for j in 0..n {
+			self.r[(0, j)] = self.r[(0, j)] + w[0] * v[j];
+		}
+		// Restore the triangular shape
+		for k in 0..::std::cmp::min(m - 1, n) {
+			let (c, s) = givens(self.r[(k, k)], self.r[(k + 1, k)]);
+			self.rotate(k, k + 1, c, s, k);
+			self.r[(k + 1, k)] = T::zero();
+		}
+	}
+
+	// Applies the rotation [c s; -s c] to rows `i`, `j` of R from column
+	// `from` on, and its transpose to columns `i`, `j` of Q.
+	fn rotate(&mut self, i: usize, j: usize, c: T, s: T, from: usize) {
+		for l in from..self.r.ncols() {
+			let (a, b) = (self.r[(i, l)], self.r[(j, l)]);
+			self.r[(i, l)] = c * a + s * b;
+			self.r[(j, l)] = c * b - s * a;
+		}
+		for l in 0..self.q.nrows() {
+			let (a, b) = (self.q[(l, i)], self.q[(l, j)]);
+			self.q[(l, i)] = c * a + s * b;
+			self.q[(l, j)] = c * b - s * a;
+		}
+	}
+}
+
+// Rotation with c * a + s * b = hypot(a, b) and c * b - s * a = 0.
+pub(crate) fn givens<T: Float>(a: T, b: T) -> (T, T) {
+	let r = a.hypot(b);
+	if r == T::zero() {
+		(T::one(), T::zero())
+	} else {
+		(a / r, b / r)
+	}
+}
+
+impl<T: Float> ColPivQr<T> {
+	pub fn q(&self) -> Matrix<T> {
+		self.q.clone()
+	}
+
+	pub fn r(&self) -> Matrix<T> {
+		self.r.clone()
+	}
+
+	/// The permutation matrix `P`.
+	pub fn p(&self) -> Matrix<T> {
+		let n = self.perm.len();
+		let mut p = Matrix::new_zero(n, n);
+		for (j, &i) in self.perm.iter().enumerate() {
+			p[(i, j)] = T::one();
+		}
+		p
+	}
+
+	/// Column `j` of `A * P` is column `permutation()[j]` of `A`.
+	pub fn permutation(&self) -> &[usize] {
+		&self.perm
+	}
+
+	pub fn rank(&self) -> usize {
+		diagonal_rank(&self.r)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, Vector, LinalgError, assert_close};
+
+	#[test]
+	fn test_qr_reconstruct() {
+		let a: Matrix<f64> = Matrix::new(4,3, &[12.0,-51.0,4.0,6.0,167.0,-68.0,-4.0,24.0,-41.0,1.0,2.0,3.0]);
+		let qr = a.qr();
+		assert_close(&qr.q().matmul(&qr.r()), &a, 1e-10);
+		assert_close(&qr.thin_q().matmul(&qr.thin_r()), &a, 1e-10);
+		let mut qt = qr.q();
+		qt.transpose();
+		assert_close(&qt.matmul(&qr.q()), &Matrix::new_identity(4), 1e-12);
+		let r = qr.r();
+		for i in 0..4 {
+			for j in 0..i.min(3) {
+				assert_eq!(r[(i, j)], 0.0);
+			}
+		}
+	}
+
+	#[test]
+	fn test_least_squares() {
+		// Fit y = 1 + 2x exactly
+		let a: Matrix<f64> = Matrix::new(4,2, &[1.0,0.0,1.0,1.0,1.0,2.0,1.0,3.0]);
+		let b = Vector::new(&[1.0,3.0,5.0,7.0]);
+		let x = a.qr().solve(&b).unwrap();
+		assert!((x[0] - 1.0).abs() < 1e-12);
+		assert!((x[1] - 2.0).abs() < 1e-12);
+
+		// Inconsistent system: best fit is y = 0.7 + 1.2x
+		let b = Vector::new(&[1.0,2.0,2.0,5.0]);
+		let x = a.qr().solve(&b).unwrap();
+		assert!((x[0] - 0.7).abs() < 1e-12);
+		assert!((x[1] - 1.2).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_rank_deficient() {
+		let a: Matrix<f64> = Matrix::new(3,2, &[1.0,2.0,2.0,4.0,3.0,6.0]);
+		let qr = a.qr();
+		assert_eq!(qr.rank(), 1);
+		assert!(!qr.is_full_rank());
+		let b = Vector::new(&[1.0,2.0,3.0]);
+		assert!(qr.solve(&b).err() == Some(LinalgError::RankDeficient));
+	}
+
+	#[test]
+	fn test_col_piv_qr() {
+		let a: Matrix<f64> = Matrix::new(3,4, &[1.0,2.0,3.0,4.0,2.0,4.0,6.0,8.0,1.0,0.0,1.0,0.0]);
+		let qr = a.col_piv_qr();
+		assert_close(&qr.q().matmul(&qr.r()), &a.matmul(&qr.p()), 1e-12);
+		let r = qr.r();
+		for i in 1..3 {
+			assert!(r[(i, i)].abs() <= r[(i - 1, i - 1)].abs());
+		}
+		assert_eq!(qr.rank(), 2);
+		assert_eq!(a.rank(), 2);
+		assert_eq!(Matrix::<f64>::new_identity(3).rank(), 3);
+		assert_eq!(Matrix::<f64>::new_zero(2, 3).rank(), 0);
+	}
+
+	#[test]
+	fn test_qr_update() {
+		let a: Matrix<f64> = Matrix::new(4,3, &[12.0,-51.0,4.0,6.0,167.0,-68.0,-4.0,24.0,-41.0,1.0,2.0,3.0]);
+		let u = Vector::new(&[1.0, 0.0, -2.0, 3.0]);
+		let v = Vector::new(&[0.5, 4.0, -1.0]);
+		let mut b = a.clone();
+		b += &u.clone().to_col_matrix().matmul(&v.clone().to_row_matrix());
+
+		let mut qr = a.qr();
+		qr.update(&u, &v);
+		assert_close(&qr.q().matmul(&qr.r()), &b, 1e-10);
+		let mut qt = qr.q();
+		qt.transpose();
+		assert_close(&qt.matmul(&qr.q()), &Matrix::new_identity(4), 1e-12);
+		let r = qr.r();
+		for i in 0..4 {
+			for j in 0..i.min(3) {
+				assert_eq!(r[(i, j)], 0.0);
+			}
+		}
+
+		// Downdate back to A
+		let mut minus_u = u.clone();
+		minus_u.scalar(-1.0);
+		qr.update(&minus_u, &v);
+		assert_close(&qr.q().matmul(&qr.r()), &a, 1e-10);
+	}
+
+	#[test]
+	fn test_subspaces() {
+		let a: Matrix<f64> = Matrix::new(3,4, &[1.0,2.0,3.0,4.0,2.0,4.0,6.0,8.0,1.0,0.0,1.0,0.0]);
+		let col = a.column_space();
+		let left = a.left_null_space();
+		let row = a.row_space();
+		let null = a.null_space();
+		assert_eq!(col.size(), (3, 2));
+		assert_eq!(left.size(), (3, 1));
+		assert_eq!(row.size(), (4, 2));
+		assert_eq!(null.size(), (4, 2));
+
+		assert_close(&a.matmul(&null), &Matrix::new_zero(3, 2), 1e-12);
+		let mut at = a.clone();
+		at.transpose();
+		assert_close(&at.matmul(&left), &Matrix::new_zero(4, 1), 1e-12);
+		for basis in [col, left, row, null].iter() {
+			let mut bt = basis.clone();
+			bt.transpose();
+			assert_close(&bt.matmul(basis), &Matrix::new_identity(basis.ncols()), 1e-12);
+		}
+	}
+}
@@ -0,0 +1,135 @@
+use traits::{Zero, One};
+use linalg::Matrix;
+use std::ops::{Add, Sub, Mul, Div};
+
+impl<T> Matrix<T> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	/// Reduced row echelon form and the pivot columns.
+	///
+	/// The pivot in each column is the first nonzero entry at or below the
+	/// current row, so no ordering on `T` is needed and the result is exact
+	/// whenever arithmetic on `T` is, e.g. for rational numbers.
+	pub fn rref(&self) -> (Matrix<T>, Vec<usize>) {
+		let (m, n) = self.size();
+		let mut a = self.clone();
+		let mut pivots = Vec::new();
+		let mut row = 0;
+		for col in 0..n {
+			if row == m {
+				break;
+			}
+			let p = match (row..m).find(|&i| a[(i, col)] != T::zero()) {
+				Some(p) => p,
+				None => continue,
+			};
+			a.swap_rows(p, row);
+
+			let pivot = a[(row, col)];
+			for j in col..n {
+				a[(row, j)] = a[(row, j)] / pivot;
+			}
+			for i in 0..m {
+				if i == row {
+					continue;
+				}
+				let f = a[(i, col)];
+				if f == T::zero() {
+					continue;
+				}
+				for j in col..n {
+					a[(i, j)] = a[(i, j)] - f * a[(row, j)];
+				}
+			}
+			pivots.push(col);
+			row += 1;
+		}
+		(a, pivots)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::Matrix;
+	use traits::{Zero, One};
+	use std::ops::{Add, Sub, Mul, Div};
+
+	// Minimal exact rational type, always kept in lowest terms
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	struct Ratio(i64, i64);
+
+	fn gcd(a: i64, b: i64) -> i64 {
+		if b == 0 { a.abs() } else { gcd(b, a % b) }
+	}
+
+	impl Ratio {
+		fn new(n: i64, d: i64) -> Ratio {
+			let g = gcd(n, d);
+			let s = if d < 0 { -1 } else { 1 };
+			Ratio(s * n / g, s * d / g)
+		}
+	}
+
+	impl Zero for Ratio {
+		fn zero() -> Self { Ratio(0, 1) }
+	}
+
+	impl One for Ratio {
+		fn one() -> Self { Ratio(1, 1) }
+	}
+
+	impl Add for Ratio {
+		type Output = Ratio;
+		fn add(self, o: Ratio) -> Ratio { Ratio::new(self.0 * o.1 + o.0 * self.1, self.1 * o.1) }
+	}
+
+	impl Sub for Ratio {
+		type Output = Ratio;
+		fn sub(self, o: Ratio) -> Ratio { Ratio::new(self.0 * o.1 - o.0 * self.1, self.1 * o.1) }
+	}
+
+	impl Mul for Ratio {
+		type Output = Ratio;
+		fn mul(self, o: Ratio) -> Ratio { Ratio::new(self.0 * o.0, self.1 * o.1) }
+	}
+
+	impl Div for Ratio {
+		type Output = Ratio;
+		fn div(self, o: Ratio) -> Ratio { Ratio::new(self.0 * o.1, self.1 * o.0) }
+	}
+
+	fn r(values: &[i64]) -> Vec<Ratio> {
+		values.iter().map(|&x| Ratio::new(x, 1)).collect()
+	}
+
+	#[test]
+	fn test_rref_exact() {
+		let a = Matrix::new(3,4, &r(&[2,4,1,3, 1,2,3,1, 3,6,4,4]));
+		let (rref, pivots) = a.rref();
+		assert_eq!(pivots, vec![0, 2]);
+		let expected = [
+			Ratio::new(1, 1), Ratio::new(2, 1), Ratio::new(0, 1), Ratio::new(8, 5),
+			Ratio::new(0, 1), Ratio::new(0, 1), Ratio::new(1, 1), Ratio::new(-1, 5),
+			Ratio::new(0, 1), Ratio::new(0, 1), Ratio::new(0, 1), Ratio::new(0, 1)];
+		assert!(rref.equals(&Matrix::new(3, 4, &expected)));
+	}
+
+	#[test]
+	fn test_rref_float() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[0.0,2.0,4.0,1.0,1.0,1.0,2.0,2.0,2.0]);
+		let (rref, pivots) = a.rref();
+		assert_eq!(pivots, vec![0, 1]);
+		assert!(rref.equals(&Matrix::new(3,3, &[1.0,0.0,-1.0,0.0,1.0,2.0,0.0,0.0,0.0])));
+
+		let (ident, pivots) = Matrix::<f64>::new_identity(3).rref();
+		assert_eq!(pivots, vec![0, 1, 2]);
+		assert!(ident.equals(&Matrix::new_identity(3)));
+	}
+}
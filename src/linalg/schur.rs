@@ -0,0 +1,91 @@
+use traits::Float;
+use linalg::{Matrix, LinalgError};
+use linalg::eigen::francis_qr;
+
+/// Real Schur decomposition `A = Z * T * Z^T` with orthogonal `Z` and quasi
+/// upper triangular `T`: 1x1 diagonal blocks hold real eigenvalues and 2x2
+/// blocks hold complex conjugate pairs.
+pub struct Schur<T> {
+	t: Matrix<T>,
+	z: Matrix<T>,
+	real: Vec<T>,
+	imag: Vec<T>,
+}
+
+impl<T: Float> Matrix<T> {
+	pub fn schur(&self) -> Result<Schur<T>, LinalgError> {
+		assert!(self.is_square());
+		let (mut t, mut z) = self.hessenberg().into_parts();
+		let (real, imag) = francis_qr(&mut t, Some(&mut z))?;
+		Ok(Schur {
+			t,
+			z,
+			real,
+			imag,
+		})
+	}
+}
+
+impl<T: Float> Schur<T> {
+	pub fn t(&self) -> &Matrix<T> {
+		&self.t
+	}
+
+	pub fn z(&self) -> &Matrix<T> {
+		&self.z
+	}
+
+	/// Real parts of the eigenvalues in the order of the diagonal blocks.
+	pub fn real(&self) -> &[T] {
+		&self.real
+	}
+
+	/// Imaginary parts of the eigenvalues in the order of the diagonal blocks.
+	pub fn imag(&self) -> &[T] {
+		&self.imag
+	}
+
+	/// Start index and size of each diagonal block of `T`.
+	pub fn blocks(&self) -> Vec<(usize, usize)> {
+		let n = self.t.nrows();
+		let mut blocks = Vec::new();
+		let mut i = 0;
+		while i < n {
+			let size = if i + 1 < n && self.t[(i + 1, i)] != T::zero() { 2 } else { 1 };
+			blocks.push((i, size));
+			i += size;
+		}
+		blocks
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, assert_close};
+
+	#[test]
+	fn test_schur() {
+		let a: Matrix<f64> = Matrix::new(4,4, &[
+			1.0,2.0,3.0,4.0,
+			-5.0,6.0,7.0,8.0,
+			9.0,-10.0,11.0,12.0,
+			13.0,14.0,-15.0,16.0]);
+		let schur = a.schur().unwrap();
+		let z = schur.z();
+		let mut zt = z.clone();
+		zt.transpose();
+		assert_close(&zt.matmul(z), &Matrix::new_identity(4), 1e-12);
+		assert_close(&z.matmul(schur.t()).matmul(&zt), &a, 1e-10);
+
+		let blocks = schur.blocks();
+		assert_eq!(blocks.iter().filter(|b| b.1 == 2).count(), 1);
+		let t = schur.t();
+		for i in 0..4 {
+			for j in 0..i {
+				if !blocks.contains(&(j, 2)) || i != j + 1 {
+					assert_eq!(t[(i, j)], 0.0);
+				}
+			}
+		}
+	}
+}
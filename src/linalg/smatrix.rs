@@ -0,0 +1,370 @@
+use traits::{Zero, One};
+use linalg::Matrix;
+use std::ops::{Index, IndexMut, Add, Sub, Mul, Div, AddAssign, SubAssign};
+use std::slice::{Iter, IterMut};
+
+/// Fixed-size `R x C` matrix stored inline in row-major order. Operations
+/// whose result size follows from the operands, like `matmul` and
+/// `transpose`, are checked by the type system. `kronecker`, `vercat` and
+/// `horcat` take the result size as type parameters, and a mismatch fails
+/// to build.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SMatrix<T, const R: usize, const C: usize> {
+	data: [[T; C]; R],
+}
+
+// Size checks on const parameters, evaluated when the calling function is
+// instantiated. Stable Rust can't express `R * R2` in a type yet.
+struct Product<const A: usize, const B: usize, const P: usize>;
+
+impl<const A: usize, const B: usize, const P: usize> Product<A, B, P> {
+	const CHECK: () = assert!(A * B == P, "result size must be the product of the operand sizes");
+}
+
+struct Sum<const A: usize, const B: usize, const S: usize>;
+
+impl<const A: usize, const B: usize, const S: usize> Sum<A, B, S> {
+	const CHECK: () = assert!(A + B == S, "result size must be the sum of the operand sizes");
+}
+
+impl<T, const R: usize, const C: usize> SMatrix<T, R, C> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	pub fn new(rows: [[T; C]; R]) -> Self {
+		SMatrix { data: rows }
+	}
+
+	/// Creates a matrix from `R * C` values in row-major order.
+	pub fn from_slice(values: &[T]) -> Self {
+		assert!(values.len() == R * C);
+		let mut m = Self::new_zero();
+		for (x, &y) in m.iter_mut().zip(values.iter()) {
+			*x = y;
+		}
+		m
+	}
+
+	/// Copies a dynamic matrix, which must be `R x C`.
+	pub fn from_matrix(m: &Matrix<T>) -> Self {
+		assert!(m.size() == (R, C));
+		let mut s = Self::new_zero();
+		for i in 0..R {
+			for j in 0..C {
+				s.data[i][j] = m[(i, j)];
+			}
+		}
+		s
+	}
+
+	pub fn to_matrix(&self) -> Matrix<T> {
+		Matrix::new(R, C, self.data.as_flattened())
+	}
+
+	pub fn new_zero() -> Self {
+		SMatrix { data: [[T::zero(); C]; R] }
+	}
+
+	pub fn size(&self) -> (usize, usize) {
+		(R, C)
+	}
+
+	pub fn nrows(&self) -> usize {
+		R
+	}
+
+	pub fn ncols(&self) -> usize {
+		C
+	}
+
+	pub fn is_square(&self) -> bool {
+		R == C
+	}
+
+	pub fn scalar(&mut self, other: T) {
+		for x in self.iter_mut() {
+			*x = *x * other;
+		}
+	}
+
+	pub fn hadamard(&mut self, other: &SMatrix<T, R, C>) {
+		for (x, &y) in self.iter_mut().zip(other.iter()) {
+			*x = *x * y;
+		}
+	}
+
+	/// Kronecker product. `R3` and `C3` must be `R * R2` and `C * C2`.
+	pub fn kronecker<const R2: usize, const C2: usize, const R3: usize, const C3: usize>(
+		&self, other: &SMatrix<T, R2, C2>) -> SMatrix<T, R3, C3> {
+		let () = Product::<R, R2, R3>::CHECK;
+		let () = Product::<C, C2, C3>::CHECK;
+		let mut m = SMatrix::new_zero();
+		for sr in 0..R {
+			for sc in 0..C {
+				for or in 0..R2 {
+					for oc in 0..C2 {
+						m.data[sr * R2 + or][sc * C2 + oc] = self.data[sr][sc] * other.data[or][oc];
+					}
+				}
+			}
+		}
+		m
+	}
+
+	/// Stacks `other` below `self`. `R3` must be `R + R2`.
+	pub fn vercat<const R2: usize, const R3: usize>(&self, other: &SMatrix<T, R2, C>)
+		-> SMatrix<T, R3, C> {
+		let () = Sum::<R, R2, R3>::CHECK;
+		let mut m = SMatrix::new_zero();
+		m.data[..R].copy_from_slice(&self.data);
+		m.data[R..].copy_from_slice(&other.data);
+		m
+	}
+
+	/// Places `other` to the right of `self`. `C3` must be `C + C2`.
+	pub fn horcat<const C2: usize, const C3: usize>(&self, other: &SMatrix<T, R, C2>)
+		-> SMatrix<T, R, C3> {
+		let () = Sum::<C, C2, C3>::CHECK;
+		let mut m = SMatrix::new_zero();
+		for i in 0..R {
+			m.data[i][..C].copy_from_slice(&self.data[i]);
+			m.data[i][C..].copy_from_slice(&other.data[i]);
+		}
+		m
+	}
+
+	/// Returns the transpose; unlike `Matrix::transpose` this can't work in
+	/// place since the type changes.
+	pub fn transpose(&self) -> SMatrix<T, C, R> {
+		let mut m = SMatrix::new_zero();
+		for i in 0..R {
+			for j in 0..C {
+				m.data[j][i] = self.data[i][j];
+			}
+		}
+		m
+	}
+
+	pub fn equals(&self, other: &SMatrix<T, R, C>) -> bool {
+		self.iter().zip(other.iter()).all(|(x, y)| *x == *y)
+	}
+
+	pub fn iter(&self) -> Iter<'_, T> {
+		self.data.as_flattened().iter()
+	}
+
+	pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+		self.data.as_flattened_mut().iter_mut()
+	}
+
+	pub fn matmul<const K: usize>(&self, other: &SMatrix<T, C, K>) -> SMatrix<T, R, K> {
+		let mut m = SMatrix::new_zero();
+		for i in 0..R {
+			for k in 0..C {
+				let a = self.data[i][k];
+				for j in 0..K {
+					m.data[i][j] = m.data[i][j] + a * other.data[k][j];
+				}
+			}
+		}
+		m
+	}
+
+}
+
+impl<T, const N: usize> SMatrix<T, N, N> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	pub fn new_identity() -> Self {
+		let mut m = Self::new_zero();
+		for i in 0..N {
+			m.data[i][i] = T::one();
+		}
+		m
+	}
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for SMatrix<T, R, C> {
+	type Output = T;
+
+	fn index(&self, index: (usize, usize)) -> &T {
+		&self.data[index.0][index.1]
+	}
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for SMatrix<T, R, C> {
+	fn index_mut(&mut self, index: (usize, usize)) -> &mut T {
+		&mut self.data[index.0][index.1]
+	}
+}
+
+impl<T, const R: usize, const C: usize> From<SMatrix<T, R, C>> for Matrix<T> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	fn from(m: SMatrix<T, R, C>) -> Matrix<T> {
+		m.to_matrix()
+	}
+}
+
+// In-place sums are `+=` and `-=` rather than inherent `add` and `sub`
+// methods, which `Add` and `Sub` would shadow, as for `Matrix`.
+impl<T, const R: usize, const C: usize> AddAssign for SMatrix<T, R, C> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	fn add_assign(&mut self, other: SMatrix<T, R, C>) {
+		for (x, &y) in self.iter_mut().zip(other.iter()) {
+			*x = *x + y;
+		}
+	}
+}
+
+impl<T, const R: usize, const C: usize> SubAssign for SMatrix<T, R, C> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	fn sub_assign(&mut self, other: SMatrix<T, R, C>) {
+		for (x, &y) in self.iter_mut().zip(other.iter()) {
+			*x = *x - y;
+		}
+	}
+}
+
+impl<T, const R: usize, const C: usize> Add for SMatrix<T, R, C> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+	type Output = SMatrix<T, R, C>;
+
+	fn add(mut self, other: SMatrix<T, R, C>) -> SMatrix<T, R, C> {
+		self += other;
+		self
+	}
+}
+
+impl<T, const R: usize, const C: usize> Sub for SMatrix<T, R, C> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+	type Output = SMatrix<T, R, C>;
+
+	fn sub(mut self, other: SMatrix<T, R, C>) -> SMatrix<T, R, C> {
+		self -= other;
+		self
+	}
+}
+
+impl<T, const R: usize, const C: usize, const K: usize> Mul<SMatrix<T, C, K>> for SMatrix<T, R, C> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+	type Output = SMatrix<T, R, K>;
+
+	fn mul(self, other: SMatrix<T, C, K>) -> SMatrix<T, R, K> {
+		self.matmul(&other)
+	}
+}
+
+impl<T, const R: usize, const C: usize> Mul<T> for SMatrix<T, R, C> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+	type Output = SMatrix<T, R, C>;
+
+	fn mul(mut self, other: T) -> SMatrix<T, R, C> {
+		self.scalar(other);
+		self
+	}
+}
+
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, SMatrix};
+
+	#[test]
+	fn test_smatrix() {
+		let a = SMatrix::new([[1, 2, 3], [4, 5, 6]]);
+		let i: SMatrix<i32, 3, 3> = SMatrix::new_identity();
+		assert_eq!(a * i, a);
+		assert_eq!(a.transpose(), SMatrix::new([[1, 4], [2, 5], [3, 6]]));
+		assert_eq!(a * a.transpose(), SMatrix::new([[14, 32], [32, 77]]));
+		assert_eq!(a + a, a * 2);
+
+		let mut h = a;
+		h.hadamard(&a);
+		h -= a;
+		assert!(h.equals(&SMatrix::from_slice(&[0, 2, 6, 12, 20, 30])));
+
+		let k: SMatrix<i32, 4, 6> = SMatrix::new([[1, 0], [0, 1]]).kronecker(&a);
+		assert_eq!(k[(3, 5)], 6);
+		assert_eq!(k[(0, 3)], 0);
+		assert!(k.to_matrix().equals(&Matrix::new_identity(2).kronecker(&a.to_matrix())));
+
+		let v: SMatrix<i32, 3, 3> = a.vercat(&SMatrix::new([[7, 8, 9]]));
+		assert_eq!(v.iter().cloned().collect::<Vec<_>>(), (1..10).collect::<Vec<_>>());
+		let h: SMatrix<i32, 2, 4> = a.horcat(&SMatrix::new([[0], [1]]));
+		assert_eq!(h, SMatrix::new([[1, 2, 3, 0], [4, 5, 6, 1]]));
+	}
+
+	#[test]
+	fn test_smatrix_conversions() {
+		let a = SMatrix::new([[1.0, 2.0], [3.0, 4.0]]);
+		let m: Matrix<f64> = a.into();
+		assert!(m.equals(&Matrix::new(2, 2, &[1.0, 2.0, 3.0, 4.0])));
+		let b: SMatrix<f64, 2, 2> = SMatrix::from_matrix(&m.into_transpose());
+		assert_eq!(b, a.transpose());
+	}
+}
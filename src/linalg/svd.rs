@@ -0,0 +1,314 @@
+use traits::Float;
+use linalg::{Matrix, LinalgError};
+
+/// Thin singular value decomposition `A = U * diag(s) * V^T`.
+///
+/// For an `m x n` matrix with `k = min(m, n)`, `U` is `m x k`, `V` is `n x k`,
+/// both with orthonormal columns, and the singular values are sorted in
+/// descending order.
+pub struct Svd<T> {
+	u: Matrix<T>,
+	s: Vec<T>,
+	v: Matrix<T>,
+}
+
+impl<T: Float> Matrix<T> {
+	pub fn svd(&self) -> Result<Svd<T>, LinalgError> {
+		if self.nrows >= self.ncols {
+			jacobi(self)
+		} else {
+			let mut t = self.clone();
+			t.transpose();
+			let svd = jacobi(&t)?;
+			Ok(Svd {
+				u: svd.v,
+				s: svd.s,
+				v: svd.u,
+			})
+		}
+	}
+
+	pub fn singular_values(&self) -> Result<Vec<T>, LinalgError> {
+		Ok(self.svd()?.s)
+	}
+
+	/// Moore-Penrose pseudo-inverse using the default tolerance.
+	pub fn pseudo_inverse(&self) -> Result<Matrix<T>, LinalgError> {
+		let svd = self.svd()?;
+		let tol = svd.default_tolerance();
+		Ok(svd.pseudo_inverse(tol))
+	}
+
+	/// 2-norm condition number, `s_max / s_min`.
+	pub fn condition_number(&self) -> Result<T, LinalgError> {
+		Ok(self.svd()?.condition_number())
+	}
+}
+
+impl<T: Float> Svd<T> {
+	pub fn u(&self) -> &Matrix<T> {
+		&self.u
+	}
+
+	pub fn singular_values(&self) -> &[T] {
+		&self.s
+	}
+
+	pub fn v(&self) -> &Matrix<T> {
+		&self.v
+	}
+
+	/// `max(m, n) * eps * s_max`, the usual cutoff below which singular
+	/// values are treated as zero.
+	pub fn default_tolerance(&self) -> T {
+		let m = ::std::cmp::max(self.u.nrows(), self.v.nrows());
+		let max = self.s.first().map_or(T::zero(), |&x| x);
+		T::from_f64(m as f64) * T::epsilon() * max
+	}
+
+	/// Number of singular values larger than `tol`.
+	pub fn rank(&self, tol: T) -> usize {
+		self.s.iter().filter(|&&x| x > tol).count()
+	}
+
+	/// Pseudo-inverse, inverting only singular values larger than `tol`.
+	pub fn pseudo_inverse(&self, tol: T) -> Matrix<T> {
+		let (m, n) = (self.u.nrows(), self.v.nrows());
+		let mut p = Matrix::new_zero(n, m);
+		for (k, &s) in self.s.iter().enumerate() {
+			if s <= tol {
+				continue;
+			}
+			for i in 0..n {
+				let a = self.v[(i, k)] / s;
+				for j in 0..m {
+					p[(i, j)] = p[(i, j)] + a * self.u[(j, k)];
+				}
+			}
+		}
+		p
+	}
+
+	/// `s_max / s_min`, infinite for a singular matrix and zero for an empty
+	/// one.
+	pub fn condition_number(&self) -> T {
+		match (self.s.first(), self.s.last()) {
+			(_, Some(&min)) if min == T::zero() => T::infinity(),
+			(Some(&max), Some(&min)) => max / min,
+			_ => T::zero(),
+		}
+	}
+
+	/// The best rank `k` approximation in both the 2-norm and the Frobenius
+	/// norm.
+	pub fn rank_k_approximation(&self, k: usize) -> Matrix<T> {
+		let (m, n) = (self.u.nrows(), self.v.nrows());
+		let mut a = Matrix::new_zero(m, n);
+		for (l, &s) in self.s.iter().enumerate().take(k) {
+			for i in 0..m {
+				let us = self.u[(i, l)] * s;
+				for j in 0..n {
+					a[(i, j)] = a[(i, j)] + us * self.v[(j, l)];
+				}
+			}
+		}
+		a
+	}
+}
+
+// One-sided Jacobi SVD for `m >= n`.
+fn jacobi<T: Float>(a: &Matrix<T>) -> Result<Svd<T>, LinalgError> {
+	let (m, n) = a.size();
+	let mut u = a.clone();
+	let mut v: Matrix<T> = Matrix::new_identity(n);
+	let two = T::one() + T::one();
+	let eps = T::epsilon();
+	// Columns below this squared norm are rounding noise and are left alone
+	let floor = a.iter().fold(T::zero(), |s, &x| s + x * x) * eps * eps;
+
+	let mut converged = false;
+	for _ in 0..60 {
+		let mut rotated = false;
+		for p in 0..n {
+			for q in (p + 1)..n {
+				let mut alpha = T::zero();
+				let mut beta = T::zero();
+				let mut gamma = T::zero();
+				for i in 0..m {
+					alpha = alpha + u[(i, p)] * u[(i, p)];
+					beta = beta + u[(i, q)] * u[(i, q)];
+					gamma = gamma + u[(i, p)] * u[(i, q)];
+				}
+				if alpha <= floor || beta <= floor || gamma.abs() <= eps * (alpha * beta).sqrt() {
+					continue;
+				}
+				rotated = true;
+
+				let zeta = (beta - alpha) / (two * gamma);
+				let mut t = T::one() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+				if zeta < T::zero() {
+					t = -t;
+				}
+				let c = T::one() / (T::one() + t * t).sqrt();
+				let s = c * t;
+				for i in 0..m {
+					let x = u[(i, p)];
+					let y = u[(i, q)];
+					u[(i, p)] = c * x - s * y;
+					u[(i, q)] = s * x + c * y;
+				}
+				for i in 0..n {
+					let x = v[(i, p)];
+					let y = v[(i, q)];
+					v[(i, p)] = c * x - s * y;
+					v[(i, q)] = s * x + c * y;
+				}
+			}
+		}
+		if !rotated {
+			converged = true;
+			break;
+		}
+	}
+	if !converged {
+		return Err(LinalgError::NoConvergence);
+	}
+
+	let mut s: Vec<T> = (0..n).map(|j| {
+		let mut ss = T::zero();
+		for i in 0..m {
+			ss = ss + u[(i, j)] * u[(i, j)];
+		}
+		ss.sqrt()
+	}).collect();
+
+	let mut order: Vec<usize> = (0..n).collect();
+	order.sort_by(|&a, &b| s[b].partial_cmp(&s[a]).unwrap_or(::std::cmp::Ordering::Equal));
+	let mut su = Matrix::new_zero(m, n);
+	let mut sv = Matrix::new_zero(n, n);
+	for (j, &k) in order.iter().enumerate() {
+		for i in 0..m {
+			su[(i, j)] = u[(i, k)];
+		}
+		for i in 0..n {
+			sv[(i, j)] = v[(i, k)];
+		}
+	}
+	s = order.iter().map(|&k| s[k]).collect();
+
+	let tol = T::from_f64(m as f64) * eps * s.first().map_or(T::zero(), |&x| x);
+	let mut filled = 0;
+	for (j, &sj) in s.iter().enumerate() {
+		if sj > tol {
+			for i in 0..m {
+				su[(i, j)] = su[(i, j)] / sj;
+			}
+			filled = j + 1;
+		}
+	}
+	complete_basis(&mut su, filled);
+	Ok(Svd {
+		u: su,
+		s,
+		v: sv,
+	})
+}
+
+// Replaces columns `k..` of `q` with unit vectors orthogonal to each other and
+// to the orthonormal columns `0..k`, by Gram-Schmidt on the standard basis.
+pub(crate) fn complete_basis<T: Float>(q: &mut Matrix<T>, k: usize) {
+	let (m, n) = q.size();
+	let mut col = k;
+	let mut e = 0;
+	let half = T::one() / (T::one() + T::one());
+	while col < n && e < m {
+		let mut x = vec![T::zero(); m];
+		x[e] = T::one();
+		e += 1;
+		// Two passes keep the result orthogonal to working precision
+		for _ in 0..2 {
+			for j in 0..col {
+				let mut d = T::zero();
+				for (i, xi) in x.iter().enumerate() {
+					d = d + q[(i, j)] * *xi;
+				}
+				for (i, xi) in x.iter_mut().enumerate() {
+					*xi = *xi - d * q[(i, j)];
+				}
+			}
+		}
+		let norm = x.iter().fold(T::zero(), |s, &xi| s + xi * xi).sqrt();
+		if norm <= half {
+			continue;
+		}
+		for (i, xi) in x.iter().enumerate() {
+			q[(i, col)] = *xi / norm;
+		}
+		col += 1;
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, assert_close};
+
+	fn reconstruct(a: &Matrix<f64>) {
+		let svd = a.svd().unwrap();
+		let k = svd.singular_values().len();
+		let mut s = Matrix::new_zero(k, k);
+		for i in 0..k {
+			s[(i, i)] = svd.singular_values()[i];
+		}
+		let mut vt = svd.v().clone();
+		vt.transpose();
+		assert_close(&svd.u().matmul(&s).matmul(&vt), a, 1e-10);
+		let mut ut = svd.u().clone();
+		ut.transpose();
+		assert_close(&ut.matmul(svd.u()), &Matrix::new_identity(k), 1e-10);
+		assert_close(&vt.matmul(svd.v()), &Matrix::new_identity(k), 1e-10);
+		for i in 1..k {
+			assert!(svd.singular_values()[i - 1] >= svd.singular_values()[i]);
+		}
+	}
+
+	#[test]
+	fn test_svd() {
+		let a: Matrix<f64> = Matrix::new(4,3, &[1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0,10.0,-1.0,0.0,2.0]);
+		reconstruct(&a);
+		let mut b = a.clone();
+		b.transpose();
+		reconstruct(&b);
+		// Rank deficient, the null direction still gets an orthonormal column
+		let c: Matrix<f64> = Matrix::new(3,3, &[1.0,2.0,3.0,2.0,4.0,6.0,1.0,1.0,1.0]);
+		reconstruct(&c);
+
+		let s = Matrix::new(2,2, &[3.0,0.0,0.0,-4.0]).singular_values().unwrap();
+		assert_eq!(s, vec![4.0, 3.0]);
+	}
+
+	#[test]
+	fn test_rank_and_pseudo_inverse() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[1.0,2.0,3.0,2.0,4.0,6.0,1.0,1.0,1.0]);
+		let svd = a.svd().unwrap();
+		assert_eq!(svd.rank(svd.default_tolerance()), 2);
+		let p = a.pseudo_inverse().unwrap();
+		assert_close(&a.matmul(&p).matmul(&a), &a, 1e-10);
+		assert_close(&p.matmul(&a).matmul(&p), &p, 1e-10);
+
+		let b: Matrix<f64> = Matrix::new(3,2, &[1.0,0.0,0.0,1.0,1.0,1.0]);
+		let expected: Matrix<f64> = Matrix::new(2,3, &[2.0,-1.0,1.0,-1.0,2.0,1.0]);
+		let mut e = expected.clone();
+		e.scalar(1.0 / 3.0);
+		assert_close(&b.pseudo_inverse().unwrap(), &e, 1e-12);
+	}
+
+	#[test]
+	fn test_condition_and_approximation() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[2.0,0.0,0.0,0.0,-5.0,0.0,0.0,0.0,0.5]);
+		assert!((a.condition_number().unwrap() - 10.0).abs() < 1e-12);
+		assert_eq!(Matrix::<f64>::new_zero(2, 3).condition_number().unwrap(), f64::INFINITY);
+		let approx = a.svd().unwrap().rank_k_approximation(2);
+		let expected: Matrix<f64> = Matrix::new(3,3, &[2.0,0.0,0.0,0.0,-5.0,0.0,0.0,0.0,0.0]);
+		assert_close(&approx, &expected, 1e-12);
+	}
+}
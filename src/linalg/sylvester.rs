@@ -0,0 +1,173 @@
+use traits::Float;
+use linalg::{Matrix, LinalgError};
+
+fn transposed<T: Float>(a: &Matrix<T>) -> Matrix<T> {
+	let mut t = a.clone();
+	t.transpose();
+	t
+}
+
+impl<T: Float> Matrix<T> {
+	/// Solves the Sylvester equation `A * X + X * B = C` with the
+	/// Bartels-Stewart algorithm. Fails with `Singular` if `A` and `-B` have
+	/// an eigenvalue in common.
+	pub fn solve_sylvester(&self, b: &Matrix<T>, c: &Matrix<T>) -> Result<Matrix<T>, LinalgError> {
+		solve(self, b, c, false)
+	}
+
+	/// Solves the continuous Lyapunov equation `A * X + X * A^T = Q`.
+	pub fn solve_lyapunov(&self, q: &Matrix<T>) -> Result<Matrix<T>, LinalgError> {
+		solve(self, &transposed(self), q, false)
+	}
+
+	/// Solves the discrete Lyapunov equation `A * X * A^T - X + Q = 0`. Fails
+	/// with `Singular` if two eigenvalues of `A` multiply to one.
+	pub fn solve_discrete_lyapunov(&self, q: &Matrix<T>) -> Result<Matrix<T>, LinalgError> {
+		let mut c = q.clone();
+		c.scalar(-T::one());
+		solve(self, &transposed(self), &c, true)
+	}
+}
+
+// Solves `A * X + X * B = C`, or `A * X * B - X = C` when `discrete` is set,
+// by reducing both A and B to real Schur form.
+fn solve<T: Float>(a: &Matrix<T>, b: &Matrix<T>, c: &Matrix<T>, discrete: bool)
+	-> Result<Matrix<T>, LinalgError> {
+	assert!(a.is_square() && b.is_square());
+	assert!(c.size() == (a.nrows(), b.nrows()));
+	let sa = a.schur()?;
+	let sb = b.schur()?;
+	let f = transposed(sa.z()).matmul(c).matmul(sb.z());
+
+	let s = sa.t();
+	let t = sb.t();
+	let tol = if discrete {
+		T::epsilon() * (T::one() + s.norm_max() * t.norm_max())
+	} else {
+		T::epsilon() * (s.norm_max() + t.norm_max())
+	};
+	let (m, n) = f.size();
+	let mut y = Matrix::new_zero(m, n);
+
+	for &(k, q) in sb.blocks().iter() {
+		// Right-hand side for column block k, with the solved columns moved
+		// over
+		let mut g = Matrix::new_zero(m, q);
+		for i in 0..m {
+			for cc in 0..q {
+				for j in 0..k {
+					g[(i, cc)] = g[(i, cc)] + y[(i, j)] * t[(j, k + cc)];
+				}
+			}
+		}
+		if discrete {
+			g = s.matmul(&g);
+		}
+
+		for &(i, p) in sa.blocks().iter().rev() {
+			let mut rhs = vec![T::zero(); p * q];
+			for r in 0..p {
+				for cc in 0..q {
+					let mut x = f[(i + r, k + cc)] - g[(i + r, cc)];
+					for l in (i + p)..m {
+						let ylk = if discrete {
+							let mut v = T::zero();
+							for c2 in 0..q {
+								v = v + y[(l, k + c2)] * t[(k + c2, k + cc)];
+							}
+							v
+						} else {
+							y[(l, k + cc)]
+						};
+						x = x - s[(i + r, l)] * ylk;
+					}
+					rhs[r + cc * p] = x;
+				}
+			}
+
+			// Small system for vec(Y_ik), at most 4x4
+			let mut kmat = Matrix::new_zero(p * q, p * q);
+			for r in 0..p {
+				for cc in 0..q {
+					for r2 in 0..p {
+						for c2 in 0..q {
+							let sv = s[(i + r, i + r2)];
+							let tv = t[(k + c2, k + cc)];
+							let same_r = r == r2;
+							let same_c = cc == c2;
+							let v = if discrete {
+								sv * tv - if same_r && same_c { T::one() } else { T::zero() }
+							} else {
+								(if same_c { sv } else { T::zero() }) + (if same_r { tv } else { T::zero() })
+							};
+							kmat[(r + cc * p, r2 + c2 * p)] = v;
+						}
+					}
+				}
+			}
+			let lu = kmat.lu();
+			let u = lu.u();
+			if (0..(p * q)).any(|d| u[(d, d)].abs() <= tol) {
+				return Err(LinalgError::Singular);
+			}
+			let sol = lu.solve_matrix(&Matrix::new(p * q, 1, &rhs))?;
+			for r in 0..p {
+				for cc in 0..q {
+					y[(i + r, k + cc)] = sol[(r + cc * p, 0)];
+				}
+			}
+		}
+	}
+
+	Ok(sa.z().matmul(&y).matmul(&transposed(sb.z())))
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, LinalgError, assert_close};
+	use super::transposed;
+
+	#[test]
+	fn test_sylvester() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[1.0,2.0,0.0,-2.0,1.0,1.0,0.0,3.0,4.0]);
+		let b: Matrix<f64> = Matrix::new(2,2, &[2.0,-1.0,1.0,3.0]);
+		let c: Matrix<f64> = Matrix::new(3,2, &[1.0,2.0,3.0,4.0,5.0,6.0]);
+		let x = a.solve_sylvester(&b, &c).unwrap();
+		let mut lhs = a.matmul(&x);
+		lhs += &x.matmul(&b);
+		assert_close(&lhs, &c, 1e-10);
+	}
+
+	#[test]
+	fn test_sylvester_singular() {
+		let a: Matrix<f64> = Matrix::new(2,2, &[1.0,0.0,0.0,2.0]);
+		let b: Matrix<f64> = Matrix::new(1,1, &[-2.0]);
+		let c: Matrix<f64> = Matrix::new(2,1, &[1.0,1.0]);
+		assert!(a.solve_sylvester(&b, &c).err() == Some(LinalgError::Singular));
+	}
+
+	#[test]
+	fn test_lyapunov() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[-1.0,2.0,0.0,-2.0,-1.0,1.0,0.0,0.5,-3.0]);
+		let q: Matrix<f64> = Matrix::new(3,3, &[-2.0,0.0,1.0,0.0,-1.0,0.0,1.0,0.0,-3.0]);
+		let x = a.solve_lyapunov(&q).unwrap();
+		let mut lhs = a.matmul(&x);
+		lhs += &x.matmul(&transposed(&a));
+		assert_close(&lhs, &q, 1e-10);
+		assert_close(&x, &transposed(&x), 1e-10);
+	}
+
+	#[test]
+	fn test_discrete_lyapunov() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[0.5,0.4,0.0,-0.4,0.5,0.1,0.0,0.2,-0.3]);
+		let q: Matrix<f64> = Matrix::new_identity(3);
+		let x = a.solve_discrete_lyapunov(&q).unwrap();
+		let mut lhs = a.matmul(&x).matmul(&transposed(&a));
+		lhs -= &x;
+		lhs += &q;
+		assert_close(&lhs, &Matrix::new_zero(3, 3), 1e-10);
+
+		let b: Matrix<f64> = Matrix::new(2,2, &[2.0,0.0,0.0,0.5]);
+		assert!(b.solve_discrete_lyapunov(&Matrix::new_identity(2)).err() == Some(LinalgError::Singular));
+	}
+}
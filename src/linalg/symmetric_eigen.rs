@@ -0,0 +1,392 @@
+use traits::Float;
+use linalg::{Matrix, LinalgError};
+
+/// Eigen decomposition `A = V * diag(values) * V^T` of a symmetric matrix.
+/// Eigenvalues are sorted in ascending order and column `i` of `vectors` is
+/// the unit eigenvector belonging to `values[i]`.
+pub struct SymmetricEigen<T> {
+	values: Vec<T>,
+	vectors: Matrix<T>,
+}
+
+impl<T: Float> Matrix<T> {
+	/// Only the lower triangle of the matrix is read.
+	pub fn symmetric_eigen(&self) -> Result<SymmetricEigen<T>, LinalgError> {
+		assert!(self.is_square());
+		let mut v = self.clone();
+		let (mut d, mut e) = tridiagonalize(&mut v, true);
+		tridiagonal_ql(&mut d, &mut e, Some(&mut v))?;
+
+		let n = self.nrows;
+		let mut order: Vec<usize> = (0..n).collect();
+		order.sort_by(|&a, &b| d[a].partial_cmp(&d[b]).unwrap_or(::std::cmp::Ordering::Equal));
+		let mut vectors = Matrix::new_zero(n, n);
+		for (j, &k) in order.iter().enumerate() {
+			for i in 0..n {
+				vectors[(i, j)] = v[(i, k)];
+			}
+		}
+		Ok(SymmetricEigen {
+			values: order.iter().map(|&k| d[k]).collect(),
+			vectors,
+		})
+	}
+
+	/// Eigenvalues of a symmetric matrix in ascending order. Cheaper than
+	/// `symmetric_eigen` since no eigenvectors are accumulated.
+	pub fn symmetric_eigenvalues(&self) -> Result<Vec<T>, LinalgError> {
+		assert!(self.is_square());
+		let mut v = self.clone();
+		let (mut d, mut e) = tridiagonalize(&mut v, false);
+		tridiagonal_ql(&mut d, &mut e, None)?;
+		d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(::std::cmp::Ordering::Equal));
+		Ok(d)
+	}
+
+	/// Generalized eigenproblem `A * x = lambda * B * x` for symmetric `A` and
+	/// symmetric positive definite `B`, reduced to a standard one with the
+	/// Cholesky factor of `B`. The eigenvectors are `B`-orthonormal,
+	/// `X^T * B * X = I`. Only the lower triangles are read.
+	pub fn symmetric_generalized_eigen(&self, b: &Matrix<T>) -> Result<SymmetricEigen<T>, LinalgError> {
+		assert!(self.is_square() && b.size() == self.size());
+		let n = self.nrows;
+		let l = b.cholesky()?.l();
+		let mut a = self.clone();
+		for i in 0..n {
+			for j in (i + 1)..n {
+				a[(i, j)] = a[(j, i)];
+			}
+		}
+
+		// C = L^-1 * A * L^-T, using the symmetry of A for the second solve
+		let mut w = lower_solve(&l, &a);
+		w.transpose();
+		let c = lower_solve(&l, &w);
+		let eig = c.symmetric_eigen()?;
+
+		// X = L^-T * Y
+		let mut x = eig.vectors;
+		for col in 0..n {
+			for i in (0..n).rev() {
+				let mut s = x[(i, col)];
+				for k in (i + 1)..n {
+					s = s - l[(k, i)] * x[(k, col)];
+				}
+				x[(i, col)] = s / l[(i, i)];
+			}
+		}
+		Ok(SymmetricEigen {
+			values: eig.values,
+			vectors: x,
+		})
+	}
+}
+
+// L^-1 * B for lower triangular L.
+fn lower_solve<T: Float>(l: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+	let mut x = b.clone();
+	for col in 0..b.ncols() {
+		for i in 0..l.nrows() {
+			let mut s = x[(i, col)];
+			for k in 0..i {
+				s = s - l[(i, k)] * x[(k, col)];
+			}
+			x[(i, col)] = s / l[(i, i)];
+		}
+	}
+	x
+}
+
+impl<T> SymmetricEigen<T> {
+	pub fn values(&self) -> &[T] {
+		&self.values
+	}
+
+	pub fn vectors(&self) -> &Matrix<T> {
+		&self.vectors
+	}
+}
+
+// Householder reduction to symmetric tridiagonal form (EISPACK tred2).
+// Returns the diagonal and the subdiagonal, the latter stored in
+// `e[1..n]`. When `accumulate` is set `v` is overwritten with the orthogonal
+// transformation, otherwise it is left as scratch.
+fn tridiagonalize<T: Float>(v: &mut Matrix<T>, accumulate: bool) -> (Vec<T>, Vec<T>) {
+	let n = v.nrows();
+	let mut d = vec![T::zero(); n];
+	let mut e = vec![T::zero(); n];
+	if n == 0 {
+		return (d, e);
+	}
+	for j in 0..n {
+		d[j] = v[(n - 1, j)];
+	}
+
+	for i in (1..n).rev() {
+		let mut scale = T::zero();
+		let mut h = T::zero();
+		for x in d.iter().take(i) {
+			scale = scale + x.abs();
+		}
+		if scale == T::zero() {
+			e[i] = d[i - 1];
+			for j in 0..i {
+				d[j] = v[(i - 1, j)];
+				v[(i, j)] = T::zero();
+				v[(j, i)] = T::zero();
+			}
+		} else {
+			for x in d.iter_mut().take(i) {
+				*x = *x / scale;
+				h = h + *x * *x;
+			}
+			let mut f = d[i - 1];
+			let mut g = h.sqrt();
+			if f > T::zero() {
+				g = -g;
+			}
+			e[i] = scale * g;
+			h = h - f * g;
+			d[i - 1] = f - g;
+			for x in e.iter_mut().take(i) {
+				*x = T::zero();
+			}
+
+			for j in 0..i {
+				f = d[j];
+				v[(j, i)] = f;
+				g = e[j] + v[(j, j)] * f;
+				for k in (j + 1)..i {
+					g = g + v[(k, j)] * d[k];
+					e[k] = e[k] + v[(k, j)] * f;
+				}
+				e[j] = g;
+			}
+			f = T::zero();
+			for j in 0..i {
+				e[j] = e[j] / h;
+				f = f + e[j] * d[j];
+			}
+			let hh = f / (h + h);
+			for j in 0..i {
+				e[j] = e[j] - hh * d[j];
+			}
+			for j in 0..i {
+				f = d[j];
+				g = e[j];
+				for k in j..i {
+					v[(k, j)] = v[(k, j)] - (f * e[k] + g * d[k]);
+				}
+				d[j] = v[(i - 1, j)];
+				v[(i, j)] = T::zero();
+			}
+		}
+		d[i] = h;
+	}
+
+	for i in 0..(n - 1) {
+		v[(n - 1, i)] = v[(i, i)];
+		if !accumulate {
+			continue;
+		}
+		v[(i, i)] = T::one();
+		let h = d[i + 1];
+		if h != T::zero() {
+			for k in 0..(i + 1) {
+				d[k] = v[(k, i + 1)] / h;
+			}
+			for j in 0..(i + 1) {
+				let mut g = T::zero();
+				for k in 0..(i + 1) {
+					g = g + v[(k, i + 1)] * v[(k, j)];
+				}
+				for k in 0..(i + 1) {
+					v[(k, j)] = v[(k, j)] - g * d[k];
+				}
+			}
+		}
+		for k in 0..(i + 1) {
+			v[(k, i + 1)] = T::zero();
+		}
+	}
+	for j in 0..n {
+		d[j] = v[(n - 1, j)];
+		if accumulate {
+			v[(n - 1, j)] = T::zero();
+		}
+	}
+	if accumulate {
+		v[(n - 1, n - 1)] = T::one();
+	}
+	e[0] = T::zero();
+	(d, e)
+}
+
+// Implicit QL iteration on a symmetric tridiagonal matrix (EISPACK tql2).
+// On return `d` holds the unsorted eigenvalues. If `v` is given the rotations
+// are applied to its columns.
+fn tridiagonal_ql<T: Float>(d: &mut [T], e: &mut [T], mut v: Option<&mut Matrix<T>>)
+	-> Result<(), LinalgError> {
+	let n = d.len();
+	if n == 0 {
+		return Ok(());
+	}
+	for i in 1..n {
+		e[i - 1] = e[i];
+	}
+	e[n - 1] = T::zero();
+
+	let two = T::one() + T::one();
+	let eps = T::epsilon();
+	let mut f = T::zero();
+	let mut tst1 = T::zero();
+	for l in 0..n {
+		let t = d[l].abs() + e[l].abs();
+		if t > tst1 {
+			tst1 = t;
+		}
+		let mut m = l;
+		while m < n - 1 && e[m].abs() > eps * tst1 {
+			m += 1;
+		}
+
+		if m > l {
+			let mut iter = 0;
+			loop {
+				iter += 1;
+				if iter > 30 * n {
+					return Err(LinalgError::NoConvergence);
+				}
+
+				// Compute the implicit shift
+				let mut g = d[l];
+				let mut p = (d[l + 1] - g) / (two * e[l]);
+				let mut r = p.hypot(T::one());
+				if p < T::zero() {
+					r = -r;
+				}
+				d[l] = e[l] / (p + r);
+				d[l + 1] = e[l] * (p + r);
+				let dl1 = d[l + 1];
+				let mut h = g - d[l];
+				for x in d.iter_mut().skip(l + 2) {
+					*x = *x - h;
+				}
+				f = f + h;
+
+				// Implicit QL transformation
+				p = d[m];
+				let mut c = T::one();
+				let mut c2 = c;
+				let mut c3 = c;
+				let el1 = e[l + 1];
+				let mut s = T::zero();
+				let mut s2 = T::zero();
+				for i in (l..m).rev() {
+					c3 = c2;
+					c2 = c;
+					s2 = s;
+					g = c * e[i];
+					h = c * p;
+					r = p.hypot(e[i]);
+					e[i + 1] = s * r;
+					s = e[i] / r;
+					c = p / r;
+					p = c * d[i] - s * g;
+					d[i + 1] = h + s * (c * g + s * d[i]);
+					if let Some(ref mut v) = v {
+						for k in 0..n {
+							h = v[(k, i + 1)];
+							v[(k, i + 1)] = s * v[(k, i)] + c * h;
+							v[(k, i)] = c * v[(k, i)] - s * h;
+						}
+					}
+				}
+				p = -s * s2 * c3 * el1 * e[l] / dl1;
+				e[l] = s * p;
+				d[l] = c * p;
+
+				if e[l].abs() <= eps * tst1 {
+					break;
+				}
+			}
+		}
+		d[l] = d[l] + f;
+		e[l] = T::zero();
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, assert_close};
+
+	#[test]
+	fn test_symmetric_eigen() {
+		let a: Matrix<f64> = Matrix::new(4,4, &[
+			4.0,1.0,-2.0,2.0,
+			1.0,2.0,0.0,1.0,
+			-2.0,0.0,3.0,-2.0,
+			2.0,1.0,-2.0,-1.0]);
+		let eig = a.symmetric_eigen().unwrap();
+		for i in 1..4 {
+			assert!(eig.values()[i - 1] <= eig.values()[i]);
+		}
+		let v = eig.vectors();
+		let mut vt = v.clone();
+		vt.transpose();
+		assert_close(&vt.matmul(v), &Matrix::new_identity(4), 1e-12);
+		let mut d = Matrix::new_zero(4, 4);
+		for i in 0..4 {
+			d[(i, i)] = eig.values()[i];
+		}
+		assert_close(&v.matmul(&d).matmul(&vt), &a, 1e-12);
+
+		let values = a.symmetric_eigenvalues().unwrap();
+		for (x, e) in values.iter().zip(eig.values().iter()) {
+			assert!((x - e).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn test_known_eigenvalues() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[2.0,-1.0,0.0,-1.0,2.0,-1.0,0.0,-1.0,2.0]);
+		let values = a.symmetric_eigenvalues().unwrap();
+		let s = 2.0f64.sqrt();
+		let expected = [2.0 - s, 2.0, 2.0 + s];
+		for (x, e) in values.iter().zip(expected.iter()) {
+			assert!((x - e).abs() < 1e-12);
+		}
+
+		let b: Matrix<f32> = Matrix::new_identity(3);
+		let eig = b.symmetric_eigen().unwrap();
+		assert_eq!(eig.values(), &[1.0, 1.0, 1.0]);
+	}
+
+	#[test]
+	fn test_symmetric_generalized_eigen() {
+		// Spring-mass chain: stiffness K and diagonal mass M
+		let k: Matrix<f64> = Matrix::new(3,3, &[2.0,-1.0,0.0,-1.0,2.0,-1.0,0.0,-1.0,1.0]);
+		let m: Matrix<f64> = Matrix::new(3,3, &[2.0,0.0,0.0,0.0,1.0,0.0,0.0,0.0,0.5]);
+		let eig = k.symmetric_generalized_eigen(&m).unwrap();
+		let x = eig.vectors();
+		let mut xt = x.clone();
+		xt.transpose();
+		assert_close(&xt.matmul(&m).matmul(x), &Matrix::new_identity(3), 1e-12);
+		let mut d = Matrix::new_zero(3, 3);
+		for i in 0..3 {
+			d[(i, i)] = eig.values()[i];
+		}
+		assert_close(&k.matmul(x), &m.matmul(x).matmul(&d), 1e-12);
+
+		// B = I gives the standard problem
+		let values = k.symmetric_generalized_eigen(&Matrix::new_identity(3)).unwrap();
+		for (x, e) in values.values().iter().zip(k.symmetric_eigenvalues().unwrap().iter()) {
+			assert!((x - e).abs() < 1e-12);
+		}
+		assert!(k.symmetric_generalized_eigen(&k.clone()).is_ok());
+		let mut indefinite = m.clone();
+		indefinite[(1, 1)] = -1.0;
+		assert!(k.symmetric_generalized_eigen(&indefinite).is_err());
+	}
+}
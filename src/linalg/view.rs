@@ -0,0 +1,815 @@
+use traits::{Zero, One};
+use linalg::{Matrix, StorageOrder};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut, Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign};
+
+/// Borrowed, possibly strided, block of a `Matrix`. Element `(i, j)` lives
+/// `i * row_stride + j * col_stride` elements after the first one in the
+/// parent's storage.
+///
+/// Views hold raw pointers rather than slices so that disjoint but
+/// interleaved views, e.g. two columns of a row-major matrix, can coexist
+/// with mutable ones.
+pub struct MatrixView<'a, T: 'a> {
+	ptr: *const T,
+	nrows: usize,
+	ncols: usize,
+	row_stride: usize,
+	col_stride: usize,
+	marker: PhantomData<&'a T>,
+}
+
+/// Mutable counterpart of `MatrixView`; writes go straight to the parent.
+pub struct MatrixViewMut<'a, T: 'a> {
+	ptr: *mut T,
+	nrows: usize,
+	ncols: usize,
+	row_stride: usize,
+	col_stride: usize,
+	marker: PhantomData<&'a mut T>,
+}
+
+/// Row-major iterator over the elements of a `MatrixView`.
+pub struct ViewIter<'a, T: 'a> {
+	view: MatrixView<'a, T>,
+	pos: usize,
+}
+
+/// Row-major iterator over the elements of a `MatrixViewMut`.
+pub struct ViewIterMut<'a, T: 'a> {
+	view: MatrixViewMut<'a, T>,
+	pos: usize,
+}
+
+/// Iterator over the rows of a matrix or view as `1 x ncols` views.
+pub struct Rows<'a, T: 'a> {
+	view: MatrixView<'a, T>,
+	front: usize,
+	back: usize,
+}
+
+/// Iterator over the columns of a matrix or view as `nrows x 1` views.
+pub struct Cols<'a, T: 'a> {
+	view: MatrixView<'a, T>,
+	front: usize,
+	back: usize,
+}
+
+/// Iterator over the rows of a matrix or view as mutable `1 x ncols` views.
+pub struct RowsMut<'a, T: 'a> {
+	view: MatrixViewMut<'a, T>,
+	front: usize,
+	back: usize,
+}
+
+/// Iterator over the columns of a matrix or view as mutable `nrows x 1`
+/// views.
+pub struct ColsMut<'a, T: 'a> {
+	view: MatrixViewMut<'a, T>,
+	front: usize,
+	back: usize,
+}
+
+// `a + b * c`, panicking rather than wrapping on overflow so that huge
+// sizes or steps can't sneak past the bounds checks in release builds.
+fn mul_add(a: usize, b: usize, c: usize) -> usize {
+	b.checked_mul(c).and_then(|x| x.checked_add(a)).expect("view extent overflows usize")
+}
+
+// Checks that the last element of a strided block is inside `len` elements.
+fn check_bounds(len: usize, offset: usize, nrows: usize, ncols: usize, rs: usize, cs: usize) {
+	if nrows > 0 && ncols > 0 {
+		assert!(mul_add(mul_add(offset, nrows - 1, rs), ncols - 1, cs) < len);
+	}
+}
+
+// Block of `size` elements taking every `step.0`-th row and `step.1`-th
+// column from `start` on, inside a `parent` sized block with the given
+// strides. Returns the offset and strides of the block. Empty blocks get
+// offset 0 so the pointer never moves past the end of the parent.
+fn sub_block(parent: (usize, usize), strides: (usize, usize), start: (usize, usize),
+	size: (usize, usize), step: (usize, usize)) -> (usize, usize, usize) {
+	let ((row, col), (nrows, ncols), (row_step, col_step)) = (start, size, step);
+	assert!(row_step > 0 && col_step > 0);
+	assert!(row <= parent.0 && col <= parent.1);
+	let (rs, cs) = (mul_add(0, strides.0, row_step), mul_add(0, strides.1, col_step));
+	if nrows == 0 || ncols == 0 {
+		return (0, rs, cs);
+	}
+	assert!(mul_add(row, nrows - 1, row_step) < parent.0);
+	assert!(mul_add(col, ncols - 1, col_step) < parent.1);
+	(mul_add(mul_add(0, row, strides.0), col, strides.1), rs, cs)
+}
+
+impl<T> Matrix<T> {
+	// Distance between consecutive rows and columns in `v`.
+	pub(crate) fn strides(&self) -> (usize, usize) {
+		match self.order {
+			StorageOrder::RowMajor => (self.ncols, 1),
+			StorageOrder::ColMajor => (1, self.nrows),
+		}
+	}
+
+	pub fn as_view(&self) -> MatrixView<'_, T> {
+		self.view(0, 0, self.nrows, self.ncols)
+	}
+
+	pub fn as_view_mut(&mut self) -> MatrixViewMut<'_, T> {
+		let (nrows, ncols) = (self.nrows, self.ncols);
+		self.view_mut(0, 0, nrows, ncols)
+	}
+
+	/// The `nrows x ncols` block starting at `(row, col)`.
+	pub fn view(&self, row: usize, col: usize, nrows: usize, ncols: usize) -> MatrixView<'_, T> {
+		self.view_strided(row, col, nrows, ncols, 1, 1)
+	}
+
+	pub fn view_mut(&mut self, row: usize, col: usize, nrows: usize, ncols: usize) -> MatrixViewMut<'_, T> {
+		self.view_strided_mut(row, col, nrows, ncols, 1, 1)
+	}
+
+	/// Every `row_step`-th row and `col_step`-th column, starting at
+	/// `(row, col)`.
+	pub fn view_strided(&self, row: usize, col: usize, nrows: usize, ncols: usize,
+		row_step: usize, col_step: usize) -> MatrixView<'_, T> {
+		let (offset, rs, cs) = sub_block((self.nrows, self.ncols), self.strides(),
+			(row, col), (nrows, ncols), (row_step, col_step));
+		check_bounds(self.v.len(), offset, nrows, ncols, rs, cs);
+		MatrixView {
+			ptr: unsafe { self.v.as_ptr().add(offset) },
+			nrows,
+			ncols,
+			row_stride: rs,
+			col_stride: cs,
+			marker: PhantomData,
+		}
+	}
+
+	pub fn row(&self, i: usize) -> MatrixView<'_, T> {
+		self.as_view().row(i)
+	}
+
+	pub fn col(&self, j: usize) -> MatrixView<'_, T> {
+		self.as_view().col(j)
+	}
+
+	pub fn row_mut(&mut self, i: usize) -> MatrixViewMut<'_, T> {
+		let ncols = self.ncols;
+		self.view_mut(i, 0, 1, ncols)
+	}
+
+	pub fn col_mut(&mut self, j: usize) -> MatrixViewMut<'_, T> {
+		let nrows = self.nrows;
+		self.view_mut(0, j, nrows, 1)
+	}
+
+	pub fn rows(&self) -> Rows<'_, T> {
+		self.as_view().rows()
+	}
+
+	pub fn cols(&self) -> Cols<'_, T> {
+		self.as_view().cols()
+	}
+
+	pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+		let nrows = self.nrows;
+		RowsMut {
+			view: self.as_view_mut(),
+			front: 0,
+			back: nrows,
+		}
+	}
+
+	pub fn cols_mut(&mut self) -> ColsMut<'_, T> {
+		let ncols = self.ncols;
+		ColsMut {
+			view: self.as_view_mut(),
+			front: 0,
+			back: ncols,
+		}
+	}
+
+	pub fn view_strided_mut(&mut self, row: usize, col: usize, nrows: usize, ncols: usize,
+		row_step: usize, col_step: usize) -> MatrixViewMut<'_, T> {
+		let (offset, rs, cs) = sub_block((self.nrows, self.ncols), self.strides(),
+			(row, col), (nrows, ncols), (row_step, col_step));
+		check_bounds(self.v.len(), offset, nrows, ncols, rs, cs);
+		MatrixViewMut {
+			ptr: unsafe { self.v.as_mut_ptr().add(offset) },
+			nrows,
+			ncols,
+			row_stride: rs,
+			col_stride: cs,
+			marker: PhantomData,
+		}
+	}
+}
+
+impl<'a, T> MatrixView<'a, T> {
+	pub fn size(&self) -> (usize, usize) {
+		(self.nrows, self.ncols)
+	}
+
+	pub fn nrows(&self) -> usize {
+		self.nrows
+	}
+
+	pub fn ncols(&self) -> usize {
+		self.ncols
+	}
+
+	/// A block of this view, see `Matrix::view`.
+	pub fn view(&self, row: usize, col: usize, nrows: usize, ncols: usize) -> MatrixView<'a, T> {
+		self.view_strided(row, col, nrows, ncols, 1, 1)
+	}
+
+	/// A strided block of this view, see `Matrix::view_strided`.
+	pub fn view_strided(&self, row: usize, col: usize, nrows: usize, ncols: usize,
+		row_step: usize, col_step: usize) -> MatrixView<'a, T> {
+		let (offset, rs, cs) = sub_block((self.nrows, self.ncols), (self.row_stride, self.col_stride),
+			(row, col), (nrows, ncols), (row_step, col_step));
+		MatrixView {
+			ptr: unsafe { self.ptr.add(offset) },
+			nrows,
+			ncols,
+			row_stride: rs,
+			col_stride: cs,
+			marker: PhantomData,
+		}
+	}
+
+	pub fn iter(&self) -> ViewIter<'a, T> {
+		ViewIter {
+			view: *self,
+			pos: 0,
+		}
+	}
+
+	pub fn row(&self, i: usize) -> MatrixView<'a, T> {
+		self.view(i, 0, 1, self.ncols)
+	}
+
+	pub fn col(&self, j: usize) -> MatrixView<'a, T> {
+		self.view(0, j, self.nrows, 1)
+	}
+
+	pub fn rows(&self) -> Rows<'a, T> {
+		Rows {
+			view: *self,
+			front: 0,
+			back: self.nrows,
+		}
+	}
+
+	pub fn cols(&self) -> Cols<'a, T> {
+		Cols {
+			view: *self,
+			front: 0,
+			back: self.ncols,
+		}
+	}
+
+	fn get(&self, i: usize, j: usize) -> &'a T {
+		assert!(i < self.nrows && j < self.ncols);
+		unsafe { &*self.ptr.add(i * self.row_stride + j * self.col_stride) }
+	}
+}
+
+impl<'a, T> Clone for MatrixView<'a, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, T> Copy for MatrixView<'a, T> {}
+
+unsafe impl<'a, T: Sync> Send for MatrixView<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for MatrixView<'a, T> {}
+unsafe impl<'a, T: Send> Send for MatrixViewMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for MatrixViewMut<'a, T> {}
+
+impl<'a, T> Index<(usize, usize)> for MatrixView<'a, T> {
+	type Output = T;
+
+	fn index(&self, index: (usize, usize)) -> &T {
+		self.get(index.0, index.1)
+	}
+}
+
+impl<'a, T> MatrixViewMut<'a, T> {
+	pub fn size(&self) -> (usize, usize) {
+		(self.nrows, self.ncols)
+	}
+
+	pub fn nrows(&self) -> usize {
+		self.nrows
+	}
+
+	pub fn ncols(&self) -> usize {
+		self.ncols
+	}
+
+	pub fn as_view(&self) -> MatrixView<'_, T> {
+		MatrixView {
+			ptr: self.ptr,
+			nrows: self.nrows,
+			ncols: self.ncols,
+			row_stride: self.row_stride,
+			col_stride: self.col_stride,
+			marker: PhantomData,
+		}
+	}
+
+	/// A mutable block of this view, see `Matrix::view`.
+	pub fn view_mut(&mut self, row: usize, col: usize, nrows: usize, ncols: usize) -> MatrixViewMut<'_, T> {
+		self.view_strided_mut(row, col, nrows, ncols, 1, 1)
+	}
+
+	/// A mutable strided block of this view, see `Matrix::view_strided`.
+	pub fn view_strided_mut(&mut self, row: usize, col: usize, nrows: usize, ncols: usize,
+		row_step: usize, col_step: usize) -> MatrixViewMut<'_, T> {
+		let (offset, rs, cs) = sub_block((self.nrows, self.ncols), (self.row_stride, self.col_stride),
+			(row, col), (nrows, ncols), (row_step, col_step));
+		MatrixViewMut {
+			ptr: unsafe { self.ptr.add(offset) },
+			nrows,
+			ncols,
+			row_stride: rs,
+			col_stride: cs,
+			marker: PhantomData,
+		}
+	}
+
+	pub fn iter(&self) -> ViewIter<'_, T> {
+		self.as_view().iter()
+	}
+
+	pub fn row_mut(&mut self, i: usize) -> MatrixViewMut<'_, T> {
+		let ncols = self.ncols;
+		self.view_mut(i, 0, 1, ncols)
+	}
+
+	pub fn col_mut(&mut self, j: usize) -> MatrixViewMut<'_, T> {
+		let nrows = self.nrows;
+		self.view_mut(0, j, nrows, 1)
+	}
+
+	pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+		let (nrows, ncols) = (self.nrows, self.ncols);
+		RowsMut {
+			view: self.view_mut(0, 0, nrows, ncols),
+			front: 0,
+			back: nrows,
+		}
+	}
+
+	pub fn cols_mut(&mut self) -> ColsMut<'_, T> {
+		let (nrows, ncols) = (self.nrows, self.ncols);
+		ColsMut {
+			view: self.view_mut(0, 0, nrows, ncols),
+			front: 0,
+			back: ncols,
+		}
+	}
+
+	pub fn iter_mut(&mut self) -> ViewIterMut<'_, T> {
+		let (nrows, ncols) = (self.nrows, self.ncols);
+		ViewIterMut {
+			view: self.view_mut(0, 0, nrows, ncols),
+			pos: 0,
+		}
+	}
+
+	/// Converts into a view of the same lifetime as the parent borrow.
+	pub fn into_view(self) -> MatrixView<'a, T> {
+		MatrixView {
+			ptr: self.ptr,
+			nrows: self.nrows,
+			ncols: self.ncols,
+			row_stride: self.row_stride,
+			col_stride: self.col_stride,
+			marker: PhantomData,
+		}
+	}
+
+	fn ptr_at(&self, i: usize, j: usize) -> *mut T {
+		assert!(i < self.nrows && j < self.ncols);
+		unsafe { self.ptr.add(i * self.row_stride + j * self.col_stride) }
+	}
+}
+
+impl<'a, T> Index<(usize, usize)> for MatrixViewMut<'a, T> {
+	type Output = T;
+
+	fn index(&self, index: (usize, usize)) -> &T {
+		unsafe { &*self.ptr_at(index.0, index.1) }
+	}
+}
+
+impl<'a, T> IndexMut<(usize, usize)> for MatrixViewMut<'a, T> {
+	fn index_mut(&mut self, index: (usize, usize)) -> &mut T {
+		unsafe { &mut *self.ptr_at(index.0, index.1) }
+	}
+}
+
+impl<'a, T> Iterator for ViewIter<'a, T> {
+	type Item = &'a T;
+
+	fn next(&mut self) -> Option<&'a T> {
+		if self.view.ncols == 0 || self.pos >= self.view.nrows * self.view.ncols {
+			return None;
+		}
+		let (i, j) = (self.pos / self.view.ncols, self.pos % self.view.ncols);
+		self.pos += 1;
+		Some(self.view.get(i, j))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let n = self.view.nrows * self.view.ncols - self.pos;
+		(n, Some(n))
+	}
+}
+
+impl<'a, T> ExactSizeIterator for ViewIter<'a, T> {}
+
+impl<'a, T> Iterator for ViewIterMut<'a, T> {
+	type Item = &'a mut T;
+
+	fn next(&mut self) -> Option<&'a mut T> {
+		if self.view.ncols == 0 || self.pos >= self.view.nrows * self.view.ncols {
+			return None;
+		}
+		let (i, j) = (self.pos / self.view.ncols, self.pos % self.view.ncols);
+		self.pos += 1;
+		// Every element is handed out once
+		Some(unsafe { &mut *self.view.ptr_at(i, j) })
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let n = self.view.nrows * self.view.ncols - self.pos;
+		(n, Some(n))
+	}
+}
+
+impl<'a, T> ExactSizeIterator for ViewIterMut<'a, T> {}
+
+impl<'a, T> Rows<'a, T> {
+	fn line(&self, i: usize) -> MatrixView<'a, T> {
+		self.view.row(i)
+	}
+}
+
+impl<'a, T> Cols<'a, T> {
+	fn line(&self, j: usize) -> MatrixView<'a, T> {
+		self.view.col(j)
+	}
+}
+
+// Rows and columns are disjoint, so each one can be handed out with the
+// lifetime of the whole borrow.
+impl<'a, T> RowsMut<'a, T> {
+	fn line(&self, i: usize) -> MatrixViewMut<'a, T> {
+		MatrixViewMut {
+			ptr: self.view.ptr_at(i, 0),
+			nrows: 1,
+			ncols: self.view.ncols,
+			row_stride: self.view.row_stride,
+			col_stride: self.view.col_stride,
+			marker: PhantomData,
+		}
+	}
+}
+
+impl<'a, T> ColsMut<'a, T> {
+	fn line(&self, j: usize) -> MatrixViewMut<'a, T> {
+		MatrixViewMut {
+			ptr: self.view.ptr_at(0, j),
+			nrows: self.view.nrows,
+			ncols: 1,
+			row_stride: self.view.row_stride,
+			col_stride: self.view.col_stride,
+			marker: PhantomData,
+		}
+	}
+}
+
+macro_rules! lines_iter_impl {
+	($($name:ident => $item:ident),+) => {
+		$(
+			impl<'a, T> Iterator for $name<'a, T> {
+				type Item = $item<'a, T>;
+
+				fn next(&mut self) -> Option<$item<'a, T>> {
+					if self.front == self.back {
+						return None;
+					}
+					self.front += 1;
+					Some(self.line(self.front - 1))
+				}
+
+				fn size_hint(&self) -> (usize, Option<usize>) {
+					let n = self.back - self.front;
+					(n, Some(n))
+				}
+			}
+
+			impl<'a, T> DoubleEndedIterator for $name<'a, T> {
+				fn next_back(&mut self) -> Option<$item<'a, T>> {
+					if self.front == self.back {
+						return None;
+					}
+					self.back -= 1;
+					Some(self.line(self.back))
+				}
+			}
+
+			impl<'a, T> ExactSizeIterator for $name<'a, T> {}
+		)+
+	}
+}
+
+lines_iter_impl! {
+	Rows => MatrixView,
+	Cols => MatrixView,
+	RowsMut => MatrixViewMut,
+	ColsMut => MatrixViewMut
+}
+
+impl<'a, T> MatrixView<'a, T> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	/// Copies the viewed elements into a new matrix.
+	pub fn to_matrix(&self) -> Matrix<T> {
+		let values: Vec<T> = self.iter().cloned().collect();
+		Matrix::new(self.nrows, self.ncols, &values)
+	}
+
+	pub fn equals(&self, other: &MatrixView<'_, T>) -> bool {
+		self.size() == other.size() && self.iter().zip(other.iter()).all(|(x, y)| *x == *y)
+	}
+
+	fn zip_with<F: Fn(T, T) -> T>(&self, other: &MatrixView<'_, T>, f: F) -> Matrix<T> {
+		assert!(self.size() == other.size());
+		let values: Vec<T> = self.iter().zip(other.iter()).map(|(&x, &y)| f(x, y)).collect();
+		Matrix::new(self.nrows, self.ncols, &values)
+	}
+}
+
+impl<'a, T> MatrixViewMut<'a, T> where T:
+	Copy +
+	Zero +
+	One +
+	Add<Output = T> +
+	Sub<Output = T> +
+	Mul<Output = T> +
+	Div<Output = T> +
+	PartialEq {
+
+	pub fn to_matrix(&self) -> Matrix<T> {
+		self.as_view().to_matrix()
+	}
+
+	pub fn fill(&mut self, x: T) {
+		for y in self.iter_mut() {
+			*y = x;
+		}
+	}
+
+	/// Overwrites the viewed elements with those of `other`.
+	pub fn copy_from(&mut self, other: &MatrixView<'_, T>) {
+		self.zip_assign(other, |_, y| y);
+	}
+
+	fn zip_assign<F: Fn(T, T) -> T>(&mut self, other: &MatrixView<'_, T>, f: F) {
+		assert!(self.size() == other.size());
+		for (x, &y) in self.iter_mut().zip(other.iter()) {
+			*x = f(*x, y);
+		}
+	}
+}
+
+impl<'a, 'b, T> Add<MatrixView<'b, T>> for MatrixView<'a, T> where T:
+	Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq {
+	type Output = Matrix<T>;
+
+	fn add(self, other: MatrixView<'b, T>) -> Matrix<T> {
+		self.zip_with(&other, |x, y| x + y)
+	}
+}
+
+impl<'a, 'b, T> Sub<MatrixView<'b, T>> for MatrixView<'a, T> where T:
+	Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq {
+	type Output = Matrix<T>;
+
+	fn sub(self, other: MatrixView<'b, T>) -> Matrix<T> {
+		self.zip_with(&other, |x, y| x - y)
+	}
+}
+
+/// Matrix product.
+impl<'a, 'b, T> Mul<MatrixView<'b, T>> for MatrixView<'a, T> where T:
+	Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq {
+	type Output = Matrix<T>;
+
+	fn mul(self, other: MatrixView<'b, T>) -> Matrix<T> {
+		assert!(self.ncols == other.nrows);
+		let mut m = Matrix::new_zero(self.nrows, other.ncols);
+		for i in 0..self.nrows {
+			for k in 0..self.ncols {
+				let a = self[(i, k)];
+				for j in 0..other.ncols {
+					m[(i, j)] = m[(i, j)] + a * other[(k, j)];
+				}
+			}
+		}
+		m
+	}
+}
+
+/// Scalar product.
+impl<'a, T> Mul<T> for MatrixView<'a, T> where T:
+	Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq {
+	type Output = Matrix<T>;
+
+	fn mul(self, other: T) -> Matrix<T> {
+		let values: Vec<T> = self.iter().map(|&x| x * other).collect();
+		Matrix::new(self.nrows, self.ncols, &values)
+	}
+}
+
+impl<'a, 'b, T> AddAssign<MatrixView<'b, T>> for MatrixViewMut<'a, T> where T:
+	Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq {
+	fn add_assign(&mut self, other: MatrixView<'b, T>) {
+		self.zip_assign(&other, |x, y| x + y);
+	}
+}
+
+impl<'a, 'b, T> SubAssign<MatrixView<'b, T>> for MatrixViewMut<'a, T> where T:
+	Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq {
+	fn sub_assign(&mut self, other: MatrixView<'b, T>) {
+		self.zip_assign(&other, |x, y| x - y);
+	}
+}
+
+impl<'a, T> MulAssign<T> for MatrixViewMut<'a, T> where T:
+	Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq {
+	fn mul_assign(&mut self, other: T) {
+		for x in self.iter_mut() {
+			*x = *x * other;
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::Matrix;
+
+	fn numbered(nrows: usize, ncols: usize) -> Matrix<i32> {
+		let values: Vec<i32> = (0..(nrows * ncols) as i32).collect();
+		Matrix::new(nrows, ncols, &values)
+	}
+
+	#[test]
+	fn test_view() {
+		let m = numbered(4, 5);
+		let v = m.view(1, 2, 2, 3);
+		assert_eq!(v.size(), (2, 3));
+		assert_eq!(v[(0, 0)], 7);
+		assert_eq!(v[(1, 2)], 14);
+		assert_eq!(v.iter().cloned().collect::<Vec<_>>(), vec![7, 8, 9, 12, 13, 14]);
+		assert!(v.to_matrix().equals(&Matrix::new(2, 3, &[7, 8, 9, 12, 13, 14])));
+
+		// Every other row and column, and a block of that
+		let s = m.view_strided(0, 0, 2, 3, 2, 2);
+		assert_eq!(s.iter().cloned().collect::<Vec<_>>(), vec![0, 2, 4, 10, 12, 14]);
+		let t = s.view_strided(1, 1, 1, 2, 1, 1);
+		assert_eq!(t.iter().cloned().collect::<Vec<_>>(), vec![12, 14]);
+		assert!(m.as_view().equals(&m.as_view()));
+		assert_eq!(m.view(4, 5, 0, 0).iter().count(), 0);
+		assert_eq!(m.view(4, 5, 0, 0).view(0, 0, 0, 0).size(), (0, 0));
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_empty_view_out_of_bounds() {
+		let m = numbered(4, 5);
+		m.view(5, 0, 0, 0);
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_view_out_of_bounds() {
+		let m = numbered(3, 3);
+		m.view(1, 1, 2, 3);
+	}
+
+	// `2 * (usize::MAX / 2 + 1)` wraps to 0 without overflow checks
+	#[test]
+	#[should_panic]
+	fn test_view_huge_step() {
+		let m = numbered(2, 3);
+		m.view_strided(0, 0, 3, 1, usize::MAX / 2 + 1, 1);
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_view_mut_huge_step() {
+		let mut m = numbered(2, 3);
+		let mut v = m.as_view_mut();
+		v.view_strided_mut(0, 0, 1, 3, 1, usize::MAX / 2 + 1);
+	}
+
+	#[test]
+	fn test_view_mut_writes_parent() {
+		let mut m = numbered(3, 4);
+		{
+			let mut v = m.view_mut(1, 1, 2, 2);
+			v[(0, 0)] = -1;
+			v *= 10;
+			let mut w = v.view_strided_mut(1, 0, 1, 2, 1, 1);
+			w.fill(0);
+		}
+		assert!(m.equals(&Matrix::new(3, 4, &[0,1,2,3, 4,-10,60,7, 8,0,0,11])));
+
+		let ones = Matrix::new(2, 2, &[1, 1, 1, 1]);
+		{
+			let mut corners = m.view_strided_mut(0, 0, 2, 2, 2, 3);
+			corners += ones.as_view();
+			corners -= ones.as_view();
+			corners += ones.as_view();
+		}
+		assert_eq!(m[(0, 0)], 1);
+		assert_eq!(m[(0, 3)], 4);
+		assert_eq!(m[(2, 0)], 9);
+		assert_eq!(m[(2, 3)], 12);
+
+		let mut v = m.as_view_mut();
+		v.copy_from(&ones.kronecker(&Matrix::new(3, 2, &[5, 5, 5, 5, 5, 5])).view(0, 0, 3, 4));
+		assert!(v.iter().all(|&x| x == 5));
+	}
+
+	#[test]
+	fn test_view_arithmetic() {
+		let m = numbered(3, 3);
+		let a = m.view(0, 0, 2, 2);
+		let b = m.view(1, 1, 2, 2);
+		assert!((a + b).equals(&Matrix::new(2, 2, &[4, 6, 10, 12])));
+		assert!((b - a).equals(&Matrix::new(2, 2, &[4, 4, 4, 4])));
+		assert!((a * b).equals(&Matrix::new(2, 2, &[7, 8, 40, 47])));
+		assert!((a * 2).equals(&Matrix::new(2, 2, &[0, 2, 6, 8])));
+		let row = m.view(0, 0, 1, 3);
+		let col = m.view_strided(0, 2, 3, 1, 1, 1);
+		assert!((row * col).equals(&Matrix::new(1, 1, &[21])));
+	}
+
+	#[test]
+	fn test_rows_and_cols() {
+		let m = numbered(3, 4);
+		let rows = m.rows();
+		assert_eq!(rows.len(), 3);
+		let sums: Vec<i32> = rows.map(|r| r.iter().sum()).collect();
+		assert_eq!(sums, vec![6, 22, 38]);
+		let last: Vec<i32> = m.cols().next_back().unwrap().iter().cloned().collect();
+		assert_eq!(last, vec![3, 7, 11]);
+		assert_eq!(m.cols().rev().map(|c| c[(0, 0)]).collect::<Vec<_>>(), vec![3, 2, 1, 0]);
+
+		let mut cols = m.cols();
+		cols.next();
+		cols.next_back();
+		assert_eq!(cols.len(), 2);
+		assert_eq!(cols.map(|c| c[(2, 0)]).collect::<Vec<_>>(), vec![9, 10]);
+		assert!(m.row(1).equals(&m.view(1, 0, 1, 4)));
+		assert_eq!(m.view(1, 1, 2, 2).cols().nth(1).unwrap()[(1, 0)], 10);
+	}
+
+	#[test]
+	fn test_rows_and_cols_mut() {
+		let mut m = numbered(3, 3);
+		for (j, mut col) in m.cols_mut().enumerate() {
+			col *= j as i32;
+		}
+		assert!(m.equals(&Matrix::new(3, 3, &[0,1,4, 0,4,10, 0,7,16])));
+
+		// All rows are alive at once and write to distinct elements
+		let mut rows: Vec<_> = m.rows_mut().rev().collect();
+		rows[0].fill(1);
+		let first = rows[2].to_matrix();
+		rows[1] -= first.as_view();
+		assert!(m.equals(&Matrix::new(3, 3, &[0,1,4, 0,3,6, 1,1,1])));
+
+		let mut v = m.view_mut(0, 1, 3, 2);
+		for mut row in v.rows_mut() {
+			row[(0, 1)] = 9;
+		}
+		m.col_mut(0).fill(-1);
+		m.row_mut(1).fill(5);
+		assert!(m.equals(&Matrix::new(3, 3, &[-1,1,9, 5,5,5, -1,1,9])));
+	}
+}
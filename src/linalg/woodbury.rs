@@ -0,0 +1,96 @@
+use traits::Float;
+use linalg::{Matrix, Vector, LinalgError};
+
+impl<T: Float> Matrix<T> {
+	/// Sherman-Morrison formula. With `self` holding `A^-1`, returns
+	/// `(A + u * v^T)^-1` in `O(n^2)`.
+	pub fn sherman_morrison(&self, u: &Vector<T>, v: &Vector<T>) -> Result<Matrix<T>, LinalgError> {
+		assert!(self.is_square());
+		let n = self.nrows;
+		assert!(u.size() == n && v.size() == n);
+
+		// A^-1 * u and v^T * A^-1
+		let mut au = vec![T::zero(); n];
+		let mut va = vec![T::zero(); n];
+		for i in 0..n {
+			for j in 0..n {
+				au[i] = au[i] + self[(i, j)] * u[j];
+				va[j] = va[j] + v[i] * self[(i, j)];
+			}
+		}
+		let mut vau = T::zero();
+		for i in 0..n {
+			vau = vau + v[i] * au[i];
+		}
+		let denom = T::one() + vau;
+		if denom.abs() <= T::epsilon() * (T::one() + vau.abs()) {
+			return Err(LinalgError::Singular);
+		}
+
+		let mut inv = self.clone();
+		for i in 0..n {
+			for j in 0..n {
+				inv[(i, j)] = inv[(i, j)] - au[i] * va[j] / denom;
+			}
+		}
+		Ok(inv)
+	}
+
+	/// Woodbury identity. With `self` holding `A^-1` (`n x n`) returns
+	/// `(A + U * C * V)^-1` for `U` `n x k`, `C` `k x k` and `V` `k x n`,
+	/// which only needs a `k x k` inverse.
+	pub fn woodbury(&self, u: &Matrix<T>, c: &Matrix<T>, v: &Matrix<T>) -> Result<Matrix<T>, LinalgError> {
+		assert!(self.is_square());
+		let (n, k) = u.size();
+		assert!(self.nrows == n && c.size() == (k, k) && v.size() == (k, n));
+
+		// A^-1 - A^-1 * U * (C^-1 + V * A^-1 * U)^-1 * V * A^-1
+		let au = self.matmul(u);
+		let va = v.matmul(self);
+		let mut s = c.inverse()?;
+		s += &v.matmul(&au);
+		let correction = au.matmul(&s.inverse()?).matmul(&va);
+		let mut inv = self.clone();
+		inv -= &correction;
+		Ok(inv)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use linalg::{Matrix, Vector, LinalgError, assert_close};
+
+	#[test]
+	fn test_sherman_morrison() {
+		let a: Matrix<f64> = Matrix::new(3,3, &[4.0,1.0,0.0,1.0,3.0,1.0,0.0,1.0,2.0]);
+		let u = Vector::new(&[1.0, 2.0, -1.0]);
+		let v = Vector::new(&[0.5, 1.0, 0.25]);
+		let mut b = a.clone();
+		b += &u.clone().to_col_matrix().matmul(&v.clone().to_row_matrix());
+		let inv = a.inverse().unwrap().sherman_morrison(&u, &v).unwrap();
+		assert_close(&inv, &b.inverse().unwrap(), 1e-12);
+
+		// I - e_1 * e_1^T is singular
+		let e = Vector::new(&[1.0, 0.0]);
+		let mut minus_e = e.clone();
+		minus_e.scalar(-1.0);
+		let id: Matrix<f64> = Matrix::new_identity(2);
+		assert!(id.sherman_morrison(&minus_e, &e).err() == Some(LinalgError::Singular));
+	}
+
+	#[test]
+	fn test_woodbury() {
+		let a: Matrix<f64> = Matrix::new(4,4, &[
+			5.0,1.0,0.0,0.0,
+			1.0,4.0,1.0,0.0,
+			0.0,1.0,3.0,1.0,
+			0.0,0.0,1.0,6.0]);
+		let u: Matrix<f64> = Matrix::new(4,2, &[1.0,0.0,0.0,1.0,2.0,0.0,1.0,-1.0]);
+		let c: Matrix<f64> = Matrix::new(2,2, &[2.0,0.5,0.0,1.0]);
+		let v: Matrix<f64> = Matrix::new(2,4, &[1.0,1.0,0.0,0.0,0.0,-1.0,0.0,2.0]);
+		let mut b = a.clone();
+		b += &u.matmul(&c).matmul(&v);
+		let inv = a.inverse().unwrap().woodbury(&u, &c, &v).unwrap();
+		assert_close(&inv, &b.inverse().unwrap(), 1e-12);
+	}
+}
@@ -1,4 +1,4 @@
-
+use std::ops::{Add, Sub, Mul, Div, Neg};
 
 pub trait Zero: Sized {
 	fn zero() -> Self;
@@ -37,4 +37,56 @@ macro_rules! zero_one_float_impl {
     )*)
 }
 
-zero_one_float_impl! {f32 f64}
\ No newline at end of file
+zero_one_float_impl! {f32 f64}
+
+pub trait Float:
+	Copy +
+	Zero +
+	One +
+	Add<Output = Self> +
+	Sub<Output = Self> +
+	Mul<Output = Self> +
+	Div<Output = Self> +
+	Neg<Output = Self> +
+	PartialOrd {
+
+	fn abs(self) -> Self;
+	fn sqrt(self) -> Self;
+	fn ln(self) -> Self;
+	fn hypot(self, other: Self) -> Self;
+	fn atan2(self, other: Self) -> Self;
+	fn powf(self, n: Self) -> Self;
+	fn epsilon() -> Self;
+	fn infinity() -> Self;
+	fn from_f64(x: f64) -> Self;
+	fn to_f64(self) -> f64;
+}
+
+macro_rules! float_impl {
+    ($($t:ident)*) => ($(
+    	impl Float for $t {
+    		#[inline]
+    		fn abs(self) -> Self { <$t>::abs(self) }
+    		#[inline]
+    		fn sqrt(self) -> Self { <$t>::sqrt(self) }
+    		#[inline]
+    		fn ln(self) -> Self { <$t>::ln(self) }
+    		#[inline]
+    		fn hypot(self, other: Self) -> Self { <$t>::hypot(self, other) }
+    		#[inline]
+    		fn atan2(self, other: Self) -> Self { <$t>::atan2(self, other) }
+    		#[inline]
+    		fn powf(self, n: Self) -> Self { <$t>::powf(self, n) }
+    		#[inline]
+    		fn epsilon() -> Self { <$t>::EPSILON }
+    		#[inline]
+    		fn infinity() -> Self { <$t>::INFINITY }
+    		#[inline]
+    		fn from_f64(x: f64) -> Self { x as $t }
+    		#[inline]
+    		fn to_f64(self) -> f64 { self as f64 }
+    	}
+    )*)
+}
+
+float_impl! {f32 f64}
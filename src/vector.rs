@@ -1,170 +1,258 @@
-use traits::{Zero, One};
-use std::ops::{Add, Sub, Mul, Div, Index, IndexMut};
-use std::slice::{Iter, IterMut};
-
-
-pub struct VectorN<T> {
-	v: Vec<T>,
-}
-
-impl<T: Zero + Copy> VectorN<T> {
-	pub fn new(v: &[T]) -> Self {
-		VectorN {
-			v: v.to_vec(),
-		}
-	}
-
-	pub fn new_zero(size: usize) -> Self {
-		VectorN {
-			v: vec![T::zero(); size],
-		}
-	}
-
-	pub fn size(&self) -> usize {
-		self.v.len()
-	}
-
-	pub fn iter(&mut self) -> Iter<T> {
-		self.v.iter()
-	}
-
-	pub fn iter_mut(&mut self) -> IterMut<T> {
-		self.v.iter_mut()
-	}
+use traits::{Zero, Float};
+use std::ops::{Add, Sub, Mul, Div, Neg, Index, IndexMut, AddAssign, SubAssign, MulAssign, DivAssign};
+
+macro_rules! vector_impl {
+    ($v:ident, $i0:literal $f0:ident $($i:literal $f:ident)*) => (
+    	#[derive(Copy, Clone, Debug, PartialEq)]
+    	pub struct $v<T> {
+    		pub $f0: T,
+    		$(pub $f: T,)*
+    	}
+
+    	impl<T: Copy> $v<T> {
+    		pub fn new($f0: T, $($f: T),*) -> Self {
+    			$v { $f0, $($f),* }
+    		}
+    	}
+
+    	impl<T: Copy + Add<Output=T> + Mul<Output=T>> $v<T> {
+    		pub fn dot(&self, other: &$v<T>) -> T {
+    			self.$f0 * other.$f0 $(+ self.$f * other.$f)*
+    		}
+
+    		pub fn length_squared(&self) -> T {
+    			self.dot(self)
+    		}
+    	}
+
+    	impl<T: Float> $v<T> {
+    		pub fn length(&self) -> T {
+    			self.length_squared().sqrt()
+    		}
+
+    		/// Scales to unit length. The zero vector becomes NaN.
+    		pub fn normalize(&mut self) {
+    			*self /= self.length();
+    		}
+
+    		/// Linear interpolation, `self` at `t = 0` and `other` at `t = 1`.
+    		pub fn lerp(&self, other: &$v<T>, t: T) -> $v<T> {
+    			*self + (*other - *self) * t
+    		}
+
+    		/// Reflection off the plane with unit normal `normal`.
+    		pub fn reflect(&self, normal: &$v<T>) -> $v<T> {
+    			let d = self.dot(normal);
+    			*self - *normal * (d + d)
+    		}
+
+    		/// Component of `self` along `onto`.
+    		pub fn project(&self, onto: &$v<T>) -> $v<T> {
+    			*onto * (self.dot(onto) / onto.length_squared())
+    		}
+
+    		/// Angle in radians, in `[0, pi]`. Uses Kahan's formula, which stays
+    		/// accurate for nearly parallel vectors where `acos` does not.
+    		pub fn angle_between(&self, other: &$v<T>) -> T {
+    			let a = *self * other.length();
+    			let b = *other * self.length();
+    			let angle = (a - b).length().atan2((a + b).length());
+    			angle + angle
+    		}
+    	}
+
+    	impl<T: Add<Output = T>> Add for $v<T> {
+    		type Output = $v<T>;
+    		fn add(self, other: $v<T>) -> $v<T> {
+    			$v { $f0: self.$f0 + other.$f0, $($f: self.$f + other.$f),* }
+    		}
+    	}
+
+    	impl<T: Sub<Output = T>> Sub for $v<T> {
+    		type Output = $v<T>;
+    		fn sub(self, other: $v<T>) -> $v<T> {
+    			$v { $f0: self.$f0 - other.$f0, $($f: self.$f - other.$f),* }
+    		}
+    	}
+
+    	impl<T: Neg<Output = T>> Neg for $v<T> {
+    		type Output = $v<T>;
+    		fn neg(self) -> $v<T> {
+    			$v { $f0: -self.$f0, $($f: -self.$f),* }
+    		}
+    	}
+
+    	/// Dot product.
+    	impl<T: Mul<Output = T> + Add<Output = T>> Mul for $v<T> {
+    		type Output = T;
+    		fn mul(self, other: $v<T>) -> T {
+    			self.$f0 * other.$f0 $(+ self.$f * other.$f)*
+    		}
+    	}
+
+    	impl<T: Mul<Output = T> + Copy> Mul<T> for $v<T> {
+    		type Output = $v<T>;
+    		fn mul(self, other: T) -> $v<T> {
+    			$v { $f0: self.$f0 * other, $($f: self.$f * other),* }
+    		}
+    	}
+
+    	impl<T: Div<Output = T> + Copy> Div<T> for $v<T> {
+    		type Output = $v<T>;
+    		fn div(self, other: T) -> $v<T> {
+    			$v { $f0: self.$f0 / other, $($f: self.$f / other),* }
+    		}
+    	}
+
+    	impl<T: Add<Output = T> + Copy> AddAssign for $v<T> {
+    		fn add_assign(&mut self, other: $v<T>) {
+    			*self = *self + other;
+    		}
+    	}
+
+    	impl<T: Sub<Output = T> + Copy> SubAssign for $v<T> {
+    		fn sub_assign(&mut self, other: $v<T>) {
+    			*self = *self - other;
+    		}
+    	}
+
+    	impl<T: Mul<Output = T> + Copy> MulAssign<T> for $v<T> {
+    		fn mul_assign(&mut self, other: T) {
+    			*self = *self * other;
+    		}
+    	}
+
+    	impl<T: Div<Output = T> + Copy> DivAssign<T> for $v<T> {
+    		fn div_assign(&mut self, other: T) {
+    			*self = *self / other;
+    		}
+    	}
+
+    	impl<T> Index<usize> for $v<T> {
+    		type Output = T;
+    		fn index(&self, index: usize) -> &T {
+    			match index {
+    				$i0 => &self.$f0,
+    				$($i => &self.$f,)*
+    				_ => panic!("vector index out of bounds: {}", index),
+    			}
+    		}
+    	}
+
+    	impl<T> IndexMut<usize> for $v<T> {
+    		fn index_mut(&mut self, index: usize) -> &mut T {
+    			match index {
+    				$i0 => &mut self.$f0,
+    				$($i => &mut self.$f,)*
+    				_ => panic!("vector index out of bounds: {}", index),
+    			}
+    		}
+    	}
+
+    	/// There is no `One`: `*` between vectors is the dot product, which has
+    	/// no identity element.
+    	impl<T: Zero> Zero for $v<T> {
+    		fn zero() -> Self {
+    			$v { $f0: T::zero(), $($f: T::zero()),* }
+    		}
+    	}
+
+    	impl Mul<$v<f32>> for f32 {
+    		type Output = $v<f32>;
+    		fn mul(self, other: $v<f32>) -> $v<f32> {
+    			other * self
+    		}
+    	}
+
+    	impl Mul<$v<f64>> for f64 {
+    		type Output = $v<f64>;
+    		fn mul(self, other: $v<f64>) -> $v<f64> {
+    			other * self
+    		}
+    	}
+    )
 }
 
-impl<T: Add<Output=T> + Copy> VectorN<T> {
-	pub fn add(&mut self, other: &VectorN<T>) {
-		assert!(self.v.len() == other.v.len());
-		for (x, y) in self.v.iter_mut().zip(other.v.iter()) {
-			*x = *x + *y;
-		}
-	}
-}
-
-impl<T: Sub<Output=T> + Copy> VectorN<T> {
-	pub fn sub(&mut self, other: &VectorN<T>) {
-		assert!(self.v.len() == other.v.len());
-		for (x, y) in self.v.iter_mut().zip(other.v.iter()) {
-			*x = *x - *y;
-		}
-	}
-}
-
-impl<T: Mul<Output=T> + Add<Output=T> + Copy + Zero> VectorN<T> {
-	pub fn scalar(&mut self, other: T) {
-		for x in self.v.iter_mut() {
-			*x = *x * other;
-		}
-	}
-
-	pub fn dot(&self, other: &VectorN<T>) -> T {
-		assert!(self.v.len() == other.v.len());
-		let mut result = T::zero();
-		for (x, y) in self.v.iter().zip(other.v.iter()) {
-			result = result + *x * *y;
-		}
-		result
-	}
-}
-
-impl<T> Index<usize> for VectorN<T> {
-	type Output = T;
-
-	fn index<'a>(&'a self, index: usize) -> &'a T {
-		&self.v[index]
-	}
-}
+vector_impl!(Vector2, 0 x 1 y);
+vector_impl!(Vector3, 0 x 1 y 2 z);
+vector_impl!(Vector4, 0 x 1 y 2 z 3 w);
 
-impl<T> IndexMut<usize> for VectorN<T> {
-	fn index_mut<'a>(&'a mut self, index: usize) -> &'a mut T {
-		&mut self.v[index]
+impl<T: Copy + Sub<Output=T> + Mul<Output=T>> Vector2<T> {
+	/// Z component of the 3D cross product, the signed area of the
+	/// parallelogram spanned by the two vectors.
+	pub fn cross(&self, other: &Vector2<T>) -> T {
+		self.x * other.y - self.y * other.x
 	}
 }
 
-
-/*
-#[derive(Copy, Clone)]
-pub struct Vector2<T> {
-	x: T,
-	y: T,
-}
-
-impl<T: Copy> Vector2<T> {
-	pub fn new(x: T, y: T) -> Self {
-		Vector2 {
-			x: x,
-			y: y,
+impl<T: Copy + Sub<Output=T> + Mul<Output=T>> Vector3<T> {
+	pub fn cross(&self, other: &Vector3<T>) -> Vector3<T> {
+		Vector3 {
+			x: self.y * other.z - self.z * other.y,
+			y: self.z * other.x - self.x * other.z,
+			z: self.x * other.y - self.y * other.x,
 		}
 	}
 }
 
-impl<T: Copy + Add<Output=T> + Sub<Output=T> + Mul<Output=T>> Vector2<T> {
-	pub fn add(&mut self, other: &Vector2<T>) {
-		self.x = self.x + other.x;
-		self.y = self.y + other.y;
-	}
-	
-	pub fn sub(&mut self, other: &Vector2<T>) {
-		self.x = self.x - other.x;
-		self.y = self.y - other.y;
-	}
-
-	pub fn scalar(&mut self, other: T) {
-		self.x = self.x * other;
-		self.y = self.y * other;
-	}
-
-	pub fn dot(&self, other: &Vector2<T>) -> T {
-		self.x * other.x + self.y * other.y
-	}
 
-	pub fn cross(&self, other: &Vector2<T>) {
-		unimplemented!();
+#[cfg(test)]
+mod test {
+	use vector::{Vector2, Vector3, Vector4};
+	use traits::Zero;
+
+	#[test]
+	fn test_vector_ops() {
+		let mut a = Vector3::new(1, 2, 3);
+		let b = Vector3::new(4, 5, 6);
+		assert_eq!(a + b, Vector3::new(5, 7, 9));
+		assert_eq!(b - a, Vector3::new(3, 3, 3));
+		assert_eq!(-a, Vector3::new(-1, -2, -3));
+		assert_eq!(a * b, 32);
+		assert_eq!(a.dot(&b), 32);
+		assert_eq!(a * 2, Vector3::new(2, 4, 6));
+		assert_eq!(b / 2, Vector3::new(2, 2, 3));
+		assert_eq!(a.cross(&b), Vector3::new(-3, 6, -3));
+		assert_eq!(Vector2::new(1, 0).cross(&Vector2::new(0, 1)), 1);
+		assert_eq!(a.cross(&b) * a, 0);
+
+		a += b;
+		a -= Vector3::new(1, 1, 1);
+		a *= 3;
+		a /= 2;
+		assert_eq!(a, Vector3::new(6, 9, 12));
+		a[2] = 0;
+		assert_eq!((a[0], a[1], a[2]), (6, 9, 0));
+		assert_eq!(Vector4::zero(), Vector4::new(0, 0, 0, 0));
+		assert_eq!(2.0 * Vector2::new(1.0, 2.0), Vector2::new(2.0, 4.0));
 	}
-}
 
-impl<T: Add<Output = T> + Copy> Add for Vector2<T> {
-	type Output = Vector2<T>;
-	fn add(self, other: Vector2<T>) -> Self {
-		Vector2 {
-			x: self.x + other.x,
-			y: self.y + other.y,
-		}
+	#[test]
+	#[should_panic]
+	fn test_vector_index_out_of_bounds() {
+		let mut a = Vector3::new(1, 2, 3);
+		a[3] = 4;
 	}
-}
 
-impl<T: Sub<Output = T> + Copy> Sub for Vector2<T> {
-	type Output = Vector2<T>;
-	fn sub(self, other: Vector2<T>) -> Self {
-		Vector2 {
-			x: self.x - other.x,
-			y: self.y - other.y,
-		}
+	#[test]
+	fn test_vector_geometry() {
+		let mut a = Vector2::new(3.0, 4.0);
+		assert_eq!(a.length(), 5.0);
+		a.normalize();
+		assert!((a.length() - 1.0f64).abs() < 1e-15);
+
+		let p = Vector3::new(1.0, 2.0, 3.0);
+		let q = Vector3::new(3.0, 2.0, 1.0);
+		assert_eq!(p.lerp(&q, 0.25), Vector3::new(1.5, 2.0, 2.5));
+		let n = Vector3::new(0.0, 1.0, 0.0);
+		assert_eq!(p.reflect(&n), Vector3::new(1.0, -2.0, 3.0));
+		assert_eq!(p.project(&Vector3::new(0.0, 0.0, 2.0)), Vector3::new(0.0, 0.0, 3.0));
+
+		let x = Vector4::new(1.0, 0.0, 0.0, 0.0);
+		let angle = x.angle_between(&Vector4::new(1.0, 1.0, 0.0, 0.0));
+		assert!((angle - ::std::f64::consts::FRAC_PI_4).abs() < 1e-15);
+		assert!((x.angle_between(&-x) - ::std::f64::consts::PI).abs() < 1e-15);
+		let tiny = Vector2::new(1.0, 1e-10).angle_between(&Vector2::new(1.0, 0.0));
+		assert!((tiny - 1e-10f64).abs() < 1e-24);
 	}
 }
-
-impl<T: Mul<Output = T> + Copy> Mul for Vector2<T> {
-	type Output = T;
-	fn mul(self, other: Vector2<T>) -> T {
-		self.x * other.c + self.y * other.y
-	}
-}
-
-
-
-
-
-
-
-
-pub struct Vector3<T> {
-	x: T,
-	y: T,
-	z: T,
-}
-
-
-
-
-*/
\ No newline at end of file